@@ -1,40 +1,27 @@
 use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::collections::HashMap;
-use utils::FileReader;
+use utils::{checked, Answer, FileReader, Solution};
 
-pub struct Day01Processor(String);
+static INPUT_NODE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?<left>\d+) +(?<right>\d+)$").expect("Expected a valid regex"));
 
-impl Day01Processor {
-    fn new() -> Self {
-        Self(String::from("./resources/aoc_24/day_01.txt"))
-    }
+pub struct Day01;
 
-    pub fn process(&self) {
-        PartOneProcessor::new(self.0.as_str()).process();
-        PartTwoProcessor::new(self.0.as_str()).process()
-    }
-}
+impl Solution for Day01 {
+    type Input = Vec<InputNode>;
 
-impl Default for Day01Processor {
-    fn default() -> Self {
-        Self::new()
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        reader.parse_lines(InputNode::try_from)
     }
-}
-
-struct PartOneProcessor(String);
 
-impl PartOneProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(DistanceCalculator::new(input).calculate()?.value().into())
     }
 
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(file_reader) {
-            Ok(p1_value) => println!("AoC 24 Day 01 Part 1: {}", p1_value.value()),
-            Err(msg) => println!("AoC 24 Day 01 Part 1: Failed with this message: {}", msg),
-        }
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(SimilarityCalculator::new(input).calculate()?.value().into())
     }
 }
 
@@ -47,31 +34,17 @@ impl PartOneValue {
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_nodes = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(InputNode::try_from)
-            .collect::<Result<Vec<InputNode>, crate::Error>>()?;
-
-        Ok(DistanceCalculator::new(input_nodes).calculate())
-    }
-}
-
 #[derive(Debug, PartialEq)]
-struct DistanceCalculator {
-    nodes: Vec<InputNode>,
+struct DistanceCalculator<'a> {
+    nodes: &'a [InputNode],
 }
 
-impl DistanceCalculator {
-    fn new(nodes: Vec<InputNode>) -> Self {
+impl<'a> DistanceCalculator<'a> {
+    fn new(nodes: &'a [InputNode]) -> Self {
         Self { nodes }
     }
 
-    fn calculate(&self) -> PartOneValue {
+    fn calculate(&self) -> Result<PartOneValue, crate::Error> {
         let mut left = vec![];
         let mut right = vec![];
 
@@ -83,43 +56,9 @@ impl DistanceCalculator {
         left.sort();
         right.sort();
 
-        let mut distance = 0i32;
-        for idx in 0..left.len() {
-            distance += (left[idx] - right[idx]).abs()
-        }
+        let distances = left.iter().zip(right.iter()).map(|(l, r)| (l - r).abs());
 
-        PartOneValue(distance)
-    }
-}
-
-struct PartTwoProcessor(String);
-
-impl PartTwoProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(file_reader) {
-            Ok(p2_value) => println!("AoC 24 Day 01 Part 2: {}", p2_value.value()),
-            Err(msg) => println!("AoC 24 Day 01 Part 2: Failed with this message: {}", msg),
-        }
-    }
-}
-
-impl TryFrom<FileReader> for PartTwoValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_nodes = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(InputNode::try_from)
-            .collect::<Result<Vec<InputNode>, crate::Error>>()?;
-
-        SimilarityCalculator::new(input_nodes).calculate()
+        Ok(PartOneValue(checked::sum(distances, "total distance")?))
     }
 }
 
@@ -139,7 +78,7 @@ struct SimilarityCalculator {
 }
 
 impl SimilarityCalculator {
-    fn new(input_nodes: Vec<InputNode>) -> Self {
+    fn new(input_nodes: &[InputNode]) -> Self {
         let mut left_nodes = vec![];
         let mut right_nodes = vec![];
 
@@ -163,7 +102,8 @@ impl SimilarityCalculator {
                 None => 0i32,
             };
 
-            total += value * score;
+            let similarity = checked::mul(*value, score, "similarity score")?;
+            total = checked::add(total, similarity, "similarity score")?;
         }
 
         Ok(PartTwoValue(total))
@@ -171,7 +111,7 @@ impl SimilarityCalculator {
 }
 
 #[derive(Debug, PartialEq)]
-struct InputNode {
+pub struct InputNode {
     pub left: i32,
     pub right: i32,
 }
@@ -180,15 +120,30 @@ impl TryFrom<String> for InputNode {
     type Error = crate::Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let re = regex::Regex::new(r"(?<left>\d+) +(?<right>\d+)$")?;
-
-        let Some(input_data) = re.captures(&value) else {
-            return Err(Self::Error::InvalidInput);
+        let Some(input_data) = INPUT_NODE_RE.captures(&value) else {
+            return Err(
+                Self::Error::invalid_input("expected two numbers separated by spaces")
+                    .with_text(value),
+            );
         };
 
+        let parse_value = |name: &str| {
+            let Some(found) = input_data.name(name) else {
+                return Err(Self::Error::invalid_input(format!(
+                    "expected a {} value",
+                    name
+                )));
+            };
+
+            found.as_str().parse::<i32>().map_err(|err| {
+                Self::Error::from(err)
+                    .with_text(found.as_str())
+                    .at_column(found.start() + 1)
+            })
+        };
         let input_node = InputNode {
-            left: input_data["left"].parse::<i32>()?,
-            right: input_data["right"].parse::<i32>()?,
+            left: parse_value("left")?,
+            right: parse_value("right")?,
         };
 
         Ok(input_node)
@@ -213,10 +168,26 @@ mod tests {
         )
     }
 
+    #[test]
+    fn invalid_input_node_diagnostic() {
+        let reader = FileReader::from_text("3   4\n4   99999999999\n");
+        let expected = [
+            " --> <input>:2:5",
+            "  |",
+            "2 | 4   99999999999",
+            "  |     ^^^^^^^^^^^ number too large to fit in target type",
+        ];
+
+        assert_eq!(
+            Some(expected.join("\n")),
+            Day01::parse(reader).unwrap_err().diagnostic()
+        )
+    }
+
     #[test]
     fn distance_calculation() {
         let distance_calculator = DistanceCalculator {
-            nodes: vec![
+            nodes: &[
                 build_input_node(3, 4),
                 build_input_node(4, 3),
                 build_input_node(2, 5),
@@ -226,7 +197,7 @@ mod tests {
             ],
         };
 
-        assert_eq!(PartOneValue(11), distance_calculator.calculate())
+        assert_eq!(PartOneValue(11), distance_calculator.calculate().unwrap())
     }
 
     #[test]
@@ -239,7 +210,7 @@ mod tests {
             build_input_node(3, 9),
             build_input_node(3, 3),
         ];
-        let similarity_calculator = SimilarityCalculator::new(input_nodes);
+        let similarity_calculator = SimilarityCalculator::new(&input_nodes);
 
         assert_eq!(PartTwoValue(31), similarity_calculator.calculate().unwrap())
     }
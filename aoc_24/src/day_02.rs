@@ -1,113 +1,57 @@
-use utils::FileReader;
+use utils::{Answer, FileReader, Solution};
 
-static SAFE_LEVEL_DIFF: [i16; 3] = [1, 2, 3];
+static SAFE_LEVEL_DIFF: [i32; 3] = [1, 2, 3];
 
-pub struct Day02Processor(String);
+pub struct Day02;
 
-impl Day02Processor {
-    fn new() -> Self {
-        Self(String::from("./resources/aoc_24/day_02.txt"))
-    }
+impl Solution for Day02 {
+    type Input = Vec<ReactorReport>;
 
-    pub fn process(&self) {
-        PartOneProcessor::new(self.0.as_str()).process();
-        PartTwoProcessor::new(self.0.as_str()).process();
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        reader.parse_lines(ReactorReport::try_from)
     }
-}
 
-impl Default for Day02Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::from(input.as_slice()).value().into())
     }
-}
 
-struct PartOneProcessor(String);
-
-impl PartOneProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(file_reader) {
-            Ok(p1_value) => println!("AoC 24 Day 02 Part 1: {}", p1_value.value()),
-            Err(msg) => println!("AoC 24 Day 02 Part 1: Failed with this message: {}", msg),
-        }
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::from(input.as_slice()).value().into())
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PartOneValue(i16);
+struct PartOneValue(usize);
 
 impl PartOneValue {
-    fn value(&self) -> i16 {
+    fn value(&self) -> usize {
         self.0
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let reactor_reports = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(ReactorReport::try_from)
-            .collect::<Result<Vec<ReactorReport>, crate::Error>>()?;
-
-        let safe_reports: Vec<ReactorReport> = reactor_reports
-            .into_iter()
-            .filter(|rr| rr.is_safe())
-            .collect();
-
-        Ok(PartOneValue(i16::try_from(safe_reports.len())?))
-    }
-}
-
-struct PartTwoProcessor(String);
-
-impl PartTwoProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(file_reader) {
-            Ok(p2_value) => println!("AoC 24 Day 02 Part 2: {}", p2_value.value()),
-            Err(msg) => println!("AoC 24 Day 02 Part 2: Failed with this message: {}", msg),
-        }
+impl From<&[ReactorReport]> for PartOneValue {
+    fn from(reactor_reports: &[ReactorReport]) -> Self {
+        PartOneValue(reactor_reports.iter().filter(|rr| rr.is_safe()).count())
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PartTwoValue(i16);
+struct PartTwoValue(usize);
 
 impl PartTwoValue {
-    fn value(&self) -> i16 {
+    fn value(&self) -> usize {
         self.0
     }
 }
 
-impl TryFrom<FileReader> for PartTwoValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let reactor_reports = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(ReactorReport::try_from)
-            .collect::<Result<Vec<ReactorReport>, crate::Error>>()?;
-
-        let safe_reports: Vec<ReactorReport> = reactor_reports
-            .into_iter()
-            .filter(|rr| rr.is_dampened_safe())
-            .collect();
-
-        Ok(PartTwoValue(i16::try_from(safe_reports.len())?))
+impl From<&[ReactorReport]> for PartTwoValue {
+    fn from(reactor_reports: &[ReactorReport]) -> Self {
+        PartTwoValue(
+            reactor_reports
+                .iter()
+                .filter(|rr| rr.is_dampened_safe())
+                .count(),
+        )
     }
 }
 
@@ -115,19 +59,22 @@ impl TryFrom<FileReader> for PartTwoValue {
 struct Level(i16);
 
 #[derive(Debug, PartialEq)]
-struct ReactorReport {
+pub struct ReactorReport {
     levels: Vec<Level>,
 }
 
 impl ReactorReport {
     fn is_safe(&self) -> bool {
-        let mut prev = self.levels[0];
+        // With no levels there is nothing to be unsafe.
+        let Some(mut prev) = self.levels.first().copied() else {
+            return true;
+        };
         let mut positives = vec![];
         let mut negatives = vec![];
 
-        for idx in 1..self.levels.len() {
-            let current = self.levels[idx];
-            let diff = current.0 - prev.0;
+        for &current in self.levels.iter().skip(1) {
+            // Widened so that levels far apart can't overflow the difference.
+            let diff = i32::from(current.0) - i32::from(prev.0);
 
             if !SAFE_LEVEL_DIFF.contains(&diff.abs()) {
                 return false;
@@ -180,7 +127,10 @@ impl TryFrom<&str> for Level {
     type Error = crate::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        Ok(Self(value.parse::<i16>()?))
+        value
+            .parse::<i16>()
+            .map(Self)
+            .map_err(|err| Self::Error::from(err).with_text(value))
     }
 }
 
@@ -260,4 +210,19 @@ mod tests {
             .unwrap()
             .is_dampened_safe())
     }
+
+    #[test]
+    fn report_unsafe_extreme_levels() {
+        assert!(!ReactorReport::try_from("-30000 30000".to_string())
+            .unwrap()
+            .is_safe())
+    }
+
+    #[test]
+    fn report_without_levels() {
+        let report = ReactorReport { levels: vec![] };
+
+        assert!(report.is_safe());
+        assert!(report.is_dampened_safe())
+    }
 }
@@ -1,16 +1,6 @@
-mod day_01;
-mod day_02;
-mod error;
+pub use utils::Error;
 
-use day_01::Day01Processor;
-use day_02::Day02Processor;
-pub use error::Error;
+pub mod day_01;
+pub mod day_02;
 
-pub struct AoC24Processor {}
-
-impl AoC24Processor {
-    pub fn process() {
-        Day01Processor::default().process();
-        Day02Processor::default().process();
-    }
-}
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
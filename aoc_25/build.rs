@@ -0,0 +1,3 @@
+fn main() -> std::io::Result<()> {
+    utils::build::generate_days()
+}
@@ -1,66 +1,29 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::ops::Deref;
-use utils::FileReader;
+use utils::{Answer, FileReader, Solution};
 
-static DAY_01_FILE: &str = "./resources/aoc_25/day_01.txt";
 static ROTATION_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?<direction>[LR])(?<distance>\d+)").expect("Expected a valid regex")
+    Regex::new(r"^(?<direction>[LR])(?<distance>\d+)$").expect("Expected a valid regex")
 });
 
-pub struct Day01Processor(String);
+pub struct Day01;
 
-impl Day01Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_01_FILE))
-    }
+impl Solution for Day01 {
+    type Input = RotationList;
 
-    pub fn process(&self) {
-        PartOneProcessor::new(self.0.as_str()).process();
-        PartTwoProcessor::new(self.0.as_str()).process();
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        RotationList::try_from(reader)
     }
-}
 
-impl Default for Day01Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(Password::from(input).landed_on_zero().into())
     }
-}
 
-struct PartOneProcessor(String);
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        let password = Password::from(input);
 
-impl PartOneProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match Password::try_from(file_reader) {
-            Ok(password) => println!("AoC 25 Day 01 Part 1: {}", password.landed_on_zero()),
-            Err(msg) => println!("AoC 25 Day 01 Part 1: Failed with this message: {}", msg),
-        }
-    }
-}
-
-struct PartTwoProcessor(String);
-
-impl PartTwoProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match Password::try_from(file_reader) {
-            Ok(password) => println!(
-                "AoC 25 Day 01 Part 2: {}",
-                password.landed_on_zero() + password.zero_clicks()
-            ),
-            Err(msg) => println!("AoC 25 Day 01 Part 2: Failed with this message: {}", msg),
-        }
+        Ok((password.landed_on_zero() + password.zero_clicks()).into())
     }
 }
 
@@ -71,10 +34,9 @@ enum Direction {
 }
 
 #[derive(Debug, PartialEq)]
-struct Rotation {
+pub struct Rotation {
     direction: Direction,
     distance: i32,
-    full_rotations: i32,
 }
 
 impl TryFrom<String> for Rotation {
@@ -82,10 +44,14 @@ impl TryFrom<String> for Rotation {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let Some(rotation_data) = ROTATION_RE.captures(&value) else {
-            return Err(Self::Error::InvalidInput);
+            return Err(
+                Self::Error::invalid_input("expected L/R followed by digits").with_text(value),
+            );
         };
 
-        let full_distance: i32 = rotation_data["distance"].parse::<i32>()?;
+        let distance: i32 = rotation_data["distance"]
+            .parse::<i32>()
+            .map_err(|err| Self::Error::from(err).with_text(&value))?;
         let direction = if &rotation_data["direction"] == "L" {
             Direction::Left
         } else {
@@ -94,14 +60,13 @@ impl TryFrom<String> for Rotation {
 
         Ok(Rotation {
             direction,
-            distance: full_distance % 100,
-            full_rotations: full_distance / 100,
+            distance,
         })
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct RotationList(Vec<Rotation>);
+pub struct RotationList(Vec<Rotation>);
 
 impl Deref for RotationList {
     type Target = Vec<Rotation>;
@@ -115,99 +80,147 @@ impl TryFrom<FileReader> for RotationList {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let rotations = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(Rotation::try_from)
-            .collect::<Result<Vec<Rotation>, crate::Error>>()?;
-
-        Ok(Self(rotations))
+        Ok(Self(reader.parse_lines(Rotation::try_from)?))
     }
 }
 
-struct SafeDial {
-    current_position: i32,
-    zero_counter: i32,
-    zero_clicks: i32,
+/// Something that happened to a [`SafeDial`] while it was being turned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DialEvent {
+    /// The dial clicked past the target this many times before the rotation
+    /// finished, not counting a rotation that ends on it.
+    PassedTarget(i64),
+    /// The rotation finished with the dial on the target.
+    LandedOnTarget,
+    /// Where the dial points once the rotation is finished.
+    Stopped(i64),
+}
+
+/// A dial numbered `0` to `size - 1` that wraps around in either direction,
+/// watching for one target position.
+#[derive(Debug, PartialEq)]
+pub struct SafeDial {
+    size: i64,
+    position: i64,
+    target: i64,
 }
 
 impl SafeDial {
-    fn new() -> Self {
-        Self {
-            current_position: 50,
-            zero_counter: 0,
-            zero_clicks: 0,
+    pub fn new(size: i64, start: i64, target: i64) -> Result<Self, crate::Error> {
+        if size <= 0 {
+            return Err(crate::Error::invalid_input(
+                "expected a dial with at least one position",
+            ));
         }
+
+        if !(0..size).contains(&start) || !(0..size).contains(&target) {
+            return Err(crate::Error::invalid_input(format!(
+                "expected the start and target to be between 0 and {}",
+                size - 1
+            )));
+        }
+
+        Ok(Self {
+            size,
+            position: start,
+            target,
+        })
     }
 
-    fn apply_rotation(&mut self, rotation: &Rotation) {
-        let previous_position = self.current_position;
+    pub fn position(&self) -> i64 {
+        self.position
+    }
 
-        let new_position = match rotation.direction {
-            Direction::Left => self.current_position - rotation.distance,
-            Direction::Right => self.current_position + rotation.distance,
+    /// Turns the dial, returning the events in the order they happened: any
+    /// passes over the target, a landing on it, and then where it stopped.
+    pub fn apply_rotation(&mut self, rotation: &Rotation) -> Vec<DialEvent> {
+        let distance = i64::from(rotation.distance);
+        let (step, clicks_to_target) = match rotation.direction {
+            Direction::Left => (-1, (self.position - self.target).rem_euclid(self.size)),
+            Direction::Right => (1, (self.target - self.position).rem_euclid(self.size)),
         };
 
-        match new_position {
-            n if n < 0 => {
-                self.current_position = 100 + n;
-                if self.current_position != 0 && previous_position != 0 {
-                    self.zero_clicks += 1;
-                }
-            }
-            n if n > 99 => {
-                self.current_position = n - 100;
-                if self.current_position != 0 && previous_position != 0 {
-                    self.zero_clicks += 1;
-                }
-            }
-            _ => self.current_position = new_position,
+        // Starting on the target means going all the way round to reach it.
+        let first_hit = if clicks_to_target == 0 {
+            self.size
+        } else {
+            clicks_to_target
+        };
+        let hits = if distance >= first_hit {
+            (distance - first_hit) / self.size + 1
+        } else {
+            0
+        };
+
+        self.position = (self.position + step * (distance % self.size)).rem_euclid(self.size);
+
+        let mut events = vec![];
+        let landed = self.position == self.target;
+        let passes = if landed && distance > 0 {
+            hits - 1
+        } else {
+            hits
+        };
+
+        if passes > 0 {
+            events.push(DialEvent::PassedTarget(passes));
+        }
+        if landed {
+            events.push(DialEvent::LandedOnTarget);
         }
+        events.push(DialEvent::Stopped(self.position));
 
-        self.zero_clicks += rotation.full_rotations;
+        events
+    }
+}
 
-        if self.current_position == 0 {
-            self.zero_counter += 1
+impl Default for SafeDial {
+    /// The safe's own dial: 100 positions, starting at 50 and watching for 0.
+    fn default() -> Self {
+        Self {
+            size: 100,
+            position: 50,
+            target: 0,
         }
     }
 }
 
-struct Password(i32, i32);
+struct Password(i64, i64);
 
 impl Password {
-    fn landed_on_zero(&self) -> i32 {
+    fn landed_on_zero(&self) -> i64 {
         self.0
     }
 
-    fn zero_clicks(&self) -> i32 {
+    fn zero_clicks(&self) -> i64 {
         self.1
     }
 }
 
-impl From<RotationList> for Password {
-    fn from(value: RotationList) -> Self {
-        let safe_dial: SafeDial = value.iter().fold(SafeDial::new(), |mut dial, rotation| {
-            dial.apply_rotation(rotation);
-            dial
-        });
-
-        Password(safe_dial.zero_counter, safe_dial.zero_clicks)
-    }
-}
-
-impl TryFrom<FileReader> for Password {
-    type Error = crate::Error;
-
-    fn try_from(value: FileReader) -> Result<Self, Self::Error> {
-        let rotations = RotationList::try_from(value)?;
+impl From<&RotationList> for Password {
+    fn from(value: &RotationList) -> Self {
+        let mut safe_dial = SafeDial::default();
+        let mut password = Password(0, 0);
+
+        for event in value
+            .iter()
+            .flat_map(|rotation| safe_dial.apply_rotation(rotation))
+        {
+            match event {
+                DialEvent::PassedTarget(times) => password.1 += times,
+                DialEvent::LandedOnTarget => password.0 += 1,
+                DialEvent::Stopped(_) => {}
+            }
+        }
 
-        Ok(Password::from(rotations))
+        password
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::Part;
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_01_sample.txt";
 
@@ -216,7 +229,6 @@ mod tests {
         let expected = Rotation {
             direction: Direction::Left,
             distance: 32,
-            full_rotations: 0,
         };
 
         assert_eq!(expected, Rotation::try_from(String::from("L32")).unwrap())
@@ -227,7 +239,6 @@ mod tests {
         let expected = Rotation {
             direction: Direction::Right,
             distance: 77,
-            full_rotations: 0,
         };
 
         assert_eq!(expected, Rotation::try_from(String::from("R77")).unwrap())
@@ -239,52 +250,42 @@ mod tests {
             Rotation {
                 direction: Direction::Left,
                 distance: 68,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 30,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Right,
                 distance: 48,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 5,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Right,
                 distance: 60,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 55,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 1,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 99,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Right,
                 distance: 14,
-                full_rotations: 0,
             },
             Rotation {
                 direction: Direction::Left,
                 distance: 82,
-                full_rotations: 0,
             },
         ];
         let expected = RotationList(rotations);
@@ -296,17 +297,121 @@ mod tests {
     #[test]
     fn password_from_rotation_list() {
         let rotations = RotationList::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
-        let password = Password::from(rotations);
+        let password = Password::from(&rotations);
 
         assert_eq!(3, password.landed_on_zero());
         assert_eq!(6, password.landed_on_zero() + password.zero_clicks())
     }
 
     #[test]
-    fn password_from_filereader() {
-        let password = Password::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
+    fn solve_from_filereader() {
+        assert_eq!(
+            Answer::from(3),
+            Day01::solve(FileReader::new(SAMPLE_FILE), Part::One).unwrap()
+        );
+        assert_eq!(
+            Answer::from(6),
+            Day01::solve(FileReader::new(SAMPLE_FILE), Part::Two).unwrap()
+        )
+    }
 
-        assert_eq!(3, password.landed_on_zero());
-        assert_eq!(6, password.landed_on_zero() + password.zero_clicks())
+    #[test]
+    fn invalid_rotation_reports_line() {
+        let reader = FileReader::from_text("L68\nX12\nR48\n");
+
+        assert_eq!(
+            "line 2: expected L/R followed by digits, got \"X12\"",
+            Day01::parse(reader).unwrap_err().to_string()
+        )
+    }
+
+    #[test]
+    fn solve_from_text() {
+        let reader = FileReader::from_text("L68\nL30\nR48\n");
+
+        assert_eq!(Answer::from(1), Day01::solve(reader, Part::One).unwrap())
+    }
+
+    #[test]
+    fn zero_clicks_beyond_i32() {
+        let reader = FileReader::from_text(&"R2147483600\n".repeat(200));
+
+        assert_eq!(
+            Answer::from(4294967200_i64),
+            Day01::solve(reader, Part::Two).unwrap()
+        )
+    }
+
+    fn rotation(text: &str) -> Rotation {
+        Rotation::try_from(String::from(text)).unwrap()
+    }
+
+    #[test]
+    fn rotation_events() {
+        let mut dial = SafeDial::default();
+
+        assert_eq!(
+            vec![DialEvent::PassedTarget(1), DialEvent::Stopped(82)],
+            dial.apply_rotation(&rotation("L68"))
+        );
+        assert_eq!(
+            vec![DialEvent::Stopped(52)],
+            dial.apply_rotation(&rotation("L30"))
+        );
+        assert_eq!(
+            vec![DialEvent::LandedOnTarget, DialEvent::Stopped(0)],
+            dial.apply_rotation(&rotation("R48"))
+        );
+        assert_eq!(
+            vec![DialEvent::PassedTarget(2), DialEvent::Stopped(50)],
+            dial.apply_rotation(&rotation("L250"))
+        );
+    }
+
+    #[test]
+    fn full_turn_from_target() {
+        let mut dial = SafeDial::new(100, 0, 0).unwrap();
+
+        assert_eq!(
+            vec![DialEvent::LandedOnTarget, DialEvent::Stopped(0)],
+            dial.apply_rotation(&rotation("R100"))
+        );
+        assert_eq!(
+            vec![DialEvent::Stopped(95)],
+            dial.apply_rotation(&rotation("L5"))
+        );
+    }
+
+    #[test]
+    fn configurable_dial() {
+        let mut dial = SafeDial::new(10, 3, 7).unwrap();
+
+        assert_eq!(
+            vec![
+                DialEvent::PassedTarget(2),
+                DialEvent::LandedOnTarget,
+                DialEvent::Stopped(7)
+            ],
+            dial.apply_rotation(&rotation("R24"))
+        );
+        assert_eq!(
+            "expected the start and target to be between 0 and 9",
+            SafeDial::new(10, 10, 0).unwrap_err().to_string()
+        );
+        assert!(SafeDial::new(0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn first_rotation_to_hit_zero() {
+        let rotations = RotationList::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
+        let mut dial = SafeDial::default();
+
+        let first_hit = rotations.iter().position(|rotation| {
+            dial.apply_rotation(rotation)
+                .iter()
+                .any(|event| !matches!(event, DialEvent::Stopped(_)))
+        });
+
+        assert_eq!(Some(0), first_hit)
     }
 }
@@ -1,80 +1,171 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use utils::{FileReader, StringUtils};
+use std::ops::{Add, Sub};
+#[cfg(test)]
+use utils::StringUtils;
+use utils::{checked, Answer, FileReader, Solution};
 
-static DAY_02_FILE: &str = "./resources/aoc_25/day_02.txt";
 static PRODUCT_RANGE_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^(?<start>\d+)-(?<end>\d+)$").expect("Expected a valid regex"));
 
-pub struct Day02Processor(String);
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<ProductRange>;
+
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        let input = reader.read_string()?;
+        let lines: Vec<&str> = input.split(',').collect();
+
+        lines
+            .iter()
+            .map(|s| ProductRange::try_from(s.to_string()))
+            .collect::<Result<Vec<ProductRange>, crate::Error>>()
+            .map_err(|err| err.at_line(1).with_source_line(&input))
+    }
 
-impl Day02Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_02_FILE))
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::try_from(input.as_slice())?.get().into())
     }
 
-    pub fn process(&self) {
-        PartOneProcessor::new(self.0.as_str()).process();
-        PartTwoProcessor::new(self.0.as_str()).process();
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::try_from(input.as_slice())?.get().into())
     }
 }
 
-impl Default for Day02Processor {
-    fn default() -> Self {
-        Self::new()
-    }
+#[derive(Debug, PartialEq)]
+pub struct ProductRange {
+    start_id: i64,
+    end_id: i64,
+}
+
+// The most digits an id can have, as `i64::MAX` has 19.
+const MAX_DIGITS: u32 = 19;
+
+/// How many invalid ids were found in a range, and what they add up to.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Tally {
+    count: i128,
+    sum: i128,
 }
 
-struct PartOneProcessor(String);
+impl Add for Tally {
+    type Output = Tally;
 
-impl PartOneProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
+    fn add(self, other: Tally) -> Tally {
+        Tally {
+            count: self.count + other.count,
+            sum: self.sum + other.sum,
+        }
     }
+}
 
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
+impl Sub for Tally {
+    type Output = Tally;
 
-        match PartOneValue::try_from(file_reader) {
-            Ok(p1_value) => println!("AoC 25 Day 02 Part 1: {}", p1_value.get()),
-            Err(msg) => println!("AoC 25 Day 02 Part 1: Failed with message: {}", msg),
+    fn sub(self, other: Tally) -> Tally {
+        Tally {
+            count: self.count - other.count,
+            sum: self.sum - other.sum,
         }
     }
 }
 
-#[allow(dead_code)]
-struct PartTwoProcessor(String);
+impl ProductRange {
+    // Tallies the ids made of the same block of digits twice, e.g. 6464.
+    fn doubled_ids(&self) -> Tally {
+        (2..=MAX_DIGITS)
+            .step_by(2)
+            .map(|length| self.repeated_ids(length, length / 2))
+            .fold(Tally::default(), Add::add)
+    }
 
-impl PartTwoProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
+    // Tallies the ids made of the same block of digits two or more times, e.g.
+    // 6464 or 646464.
+    //
+    // An id of `length` digits repeats a block of `length / p` digits for some
+    // prime `p` dividing `length`; any shorter block divides one of those. An id
+    // that repeats blocks for two primes `p` and `q` also repeats a block of
+    // `length / (p * q)` digits, so inclusion-exclusion over the sets of primes
+    // counts each id once.
+    fn repeated_block_ids(&self) -> Tally {
+        let mut tally = Tally::default();
+
+        for length in 2..=MAX_DIGITS {
+            let primes = prime_factors(length);
+
+            for subset in 1..1_usize << primes.len() {
+                let chosen = primes
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| subset & (1 << index) != 0)
+                    .map(|(_, prime)| prime);
+                let divisor: u32 = chosen.clone().product();
+                let ids = self.repeated_ids(length, length / divisor);
+
+                tally = if chosen.count() % 2 == 1 {
+                    tally + ids
+                } else {
+                    tally - ids
+                };
+            }
+        }
+
+        tally
     }
 
-    fn process(&self) {
-        // This takes a long time to run so commenting out unless actually needed
-        /*
-        let file_reader = FileReader::new(&self.0);
+    // Tallies the ids in the range with `length` digits that are a block of
+    // `block` digits repeated. Each is the block times a repunit like 10101, so
+    // the blocks that fit the range form a run whose sum has a closed form.
+    fn repeated_ids(&self, length: u32, block: u32) -> Tally {
+        let lowest = 10_i128.pow(length - 1);
+        let highest = 10_i128.pow(length) - 1;
+        let start = i128::from(self.start_id).max(lowest);
+        let end = i128::from(self.end_id).min(highest);
+
+        let repunit = highest / (10_i128.pow(block) - 1);
+        let first_block = ((start + repunit - 1) / repunit).max(10_i128.pow(block - 1));
+        let last_block = (end / repunit).min(10_i128.pow(block) - 1);
+
+        if start > end || first_block > last_block {
+            return Tally::default();
+        }
+
+        let count = last_block - first_block + 1;
 
-        match PartTwoValue::try_from(file_reader) {
-            Ok(p2_value) => println!("AoC 25 Day 02 Part 2: {}", p2_value.get()),
-            Err(msg) => println!("AoC 25 Day 02 Part 2: Failed with message: {}", msg),
+        Tally {
+            count,
+            sum: repunit * (first_block + last_block) * count / 2,
         }
-        */
-        println!("AoC 25 Day 02 Part 2: Skipped")
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct ProductRange {
-    start_id: i64,
-    end_id: i64,
+// The distinct prime factors of `n`.
+fn prime_factors(mut n: u32) -> Vec<u32> {
+    let mut factors = vec![];
+    let mut factor = 2;
+
+    while n > 1 {
+        if n.is_multiple_of(factor) {
+            factors.push(factor);
+            while n.is_multiple_of(factor) {
+                n /= factor;
+            }
+        }
+        factor += 1;
+    }
+
+    factors
 }
 
+// Brute force versions that check every id in the range, kept to cross-check
+// the closed forms above.
+#[cfg(test)]
 impl ProductRange {
     fn invalid_ids(&self) -> Vec<i64> {
         let mut invalids: Vec<i64> = vec![];
 
-        for prod_id in self.start_id..self.end_id + 1 {
+        for prod_id in self.start_id..=self.end_id {
             if ProductRange::invalid_product_id(prod_id) {
                 invalids.push(prod_id)
             }
@@ -88,7 +179,7 @@ impl ProductRange {
         let length = prod_id_str.len();
 
         // If it's odd, it can't be the same number twice
-        if length % 2 > 0 {
+        if !length.is_multiple_of(2) {
             return false;
         }
 
@@ -103,7 +194,7 @@ impl ProductRange {
     fn advanced_invalid_ids(&self) -> Vec<i64> {
         let mut invalids: Vec<i64> = vec![];
 
-        for prod_id in self.start_id..self.end_id + 1 {
+        for prod_id in self.start_id..=self.end_id {
             if ProductRange::advanced_invalid_product_id(prod_id) {
                 invalids.push(prod_id)
             }
@@ -133,75 +224,62 @@ impl TryFrom<String> for ProductRange {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let Some(product_range) = PRODUCT_RANGE_RE.captures(&value) else {
-            return Err(Self::Error::InvalidInput);
+            return Err(Self::Error::invalid_input("expected a range like 11-22").with_text(value));
         };
 
-        let start_id = product_range["start"].parse::<i64>()?;
-        let end_id = product_range["end"].parse::<i64>()?;
+        let parse_id = |id: &str| {
+            id.parse::<i64>()
+                .map_err(|err| Self::Error::from(err).with_text(&value))
+        };
+        let start_id = parse_id(&product_range["start"])?;
+        let end_id = parse_id(&product_range["end"])?;
 
         Ok(ProductRange { start_id, end_id })
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PartOneValue(i64);
+struct PartOneValue(i128);
 
 impl PartOneValue {
-    fn get(&self) -> i64 {
+    fn get(&self) -> i128 {
         self.0
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
+impl TryFrom<&[ProductRange]> for PartOneValue {
     type Error = crate::Error;
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = reader.read_string()?;
-        let lines: Vec<&str> = input.split(',').collect();
-
-        let product_ranges = lines
-            .iter()
-            .map(|s| ProductRange::try_from(s.to_string()))
-            .collect::<Result<Vec<ProductRange>, crate::Error>>()?;
-        let total = product_ranges.iter().flat_map(|pr| pr.invalid_ids()).sum();
+    fn try_from(product_ranges: &[ProductRange]) -> Result<Self, Self::Error> {
+        let sums = product_ranges.iter().map(|pr| pr.doubled_ids().sum);
 
-        Ok(PartOneValue(total))
+        Ok(PartOneValue(checked::sum(sums, "invalid id total")?))
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PartTwoValue(i64);
+struct PartTwoValue(i128);
 
-#[allow(dead_code)]
 impl PartTwoValue {
-    fn get(&self) -> i64 {
+    fn get(&self) -> i128 {
         self.0
     }
 }
 
-impl TryFrom<FileReader> for PartTwoValue {
+impl TryFrom<&[ProductRange]> for PartTwoValue {
     type Error = crate::Error;
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = reader.read_string()?;
-        let lines: Vec<&str> = input.split(',').collect();
-
-        let product_ranges = lines
-            .iter()
-            .map(|s| ProductRange::try_from(s.to_string()))
-            .collect::<Result<Vec<ProductRange>, crate::Error>>()?;
-        let total = product_ranges
-            .iter()
-            .flat_map(|pr| pr.advanced_invalid_ids())
-            .sum();
+    fn try_from(product_ranges: &[ProductRange]) -> Result<Self, Self::Error> {
+        let sums = product_ranges.iter().map(|pr| pr.repeated_block_ids().sum);
 
-        Ok(PartTwoValue(total))
+        Ok(PartTwoValue(checked::sum(sums, "invalid id total")?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::Part;
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_02_sample.txt";
 
@@ -223,6 +301,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_invalid_product_range() {
+        let reader = FileReader::from_text("11-22,95-1x5");
+
+        assert_eq!(
+            "line 1: expected a range like 11-22, got \"95-1x5\"",
+            Day02::parse(reader).unwrap_err().to_string()
+        )
+    }
+
     #[test]
     fn test_invalid_product_id() {
         assert!(ProductRange::invalid_product_id(11));
@@ -249,11 +337,25 @@ mod tests {
         assert_eq!(expected2, product_range2.invalid_ids());
     }
 
+    #[test]
+    fn test_invalid_ids_at_max_id() {
+        let product_range = ProductRange {
+            start_id: i64::MAX - 1,
+            end_id: i64::MAX,
+        };
+
+        assert!(product_range.invalid_ids().is_empty());
+        assert!(product_range.advanced_invalid_ids().is_empty());
+    }
+
     #[test]
     fn test_sum_invalid_product_ids() {
         let reader = FileReader::new(SAMPLE_FILE);
 
-        assert_eq!(1227775554, PartOneValue::try_from(reader).unwrap().get())
+        assert_eq!(
+            Answer::from(1227775554),
+            Day02::solve(reader, Part::One).unwrap()
+        )
     }
 
     #[test]
@@ -291,6 +393,63 @@ mod tests {
     fn test_sum_advanced_invalid_product_ids() {
         let reader = FileReader::new(SAMPLE_FILE);
 
-        assert_eq!(4174379265, PartTwoValue::try_from(reader).unwrap().get())
+        assert_eq!(
+            Answer::from(4174379265_i64),
+            Day02::solve(reader, Part::Two).unwrap()
+        )
+    }
+
+    // Checks the closed forms against the brute force over ranges covering
+    // every way a range can line up with the repeated ids and digit lengths.
+    #[test]
+    fn test_closed_form_matches_brute_force() {
+        let tally = |ids: Vec<i64>| Tally {
+            count: ids.len() as i128,
+            sum: ids.iter().map(|&id| i128::from(id)).sum(),
+        };
+
+        for start_id in (0..2_000).step_by(37).chain([9_990, 99_990, 123_120]) {
+            for span in [0, 1, 10, 99, 1_000, 12_345] {
+                let product_range = ProductRange {
+                    start_id,
+                    end_id: start_id + span,
+                };
+
+                assert_eq!(
+                    tally(product_range.invalid_ids()),
+                    product_range.doubled_ids()
+                );
+                assert_eq!(
+                    tally(product_range.advanced_invalid_ids()),
+                    product_range.repeated_block_ids()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_repeated_block_ids_whole_length() {
+        // Every 6 digit id that repeats a block of 1, 2 or 3 digits: 900 with
+        // blocks of 3 and 90 with blocks of 2, less the 9 counted by both.
+        let product_range = ProductRange {
+            start_id: 100_000,
+            end_id: 999_999,
+        };
+
+        assert_eq!(981, product_range.repeated_block_ids().count);
+    }
+
+    #[test]
+    fn test_invalid_ids_across_all_ids() {
+        // The totals are well past what an i64 can hold.
+        let input = vec![ProductRange {
+            start_id: 0,
+            end_id: i64::MAX,
+        }];
+        let doubled = PartOneValue::try_from(input.as_slice()).unwrap().get();
+        let repeated = PartTwoValue::try_from(input.as_slice()).unwrap().get();
+
+        assert!(doubled > i128::from(i64::MAX));
+        assert!(repeated > doubled);
     }
 }
@@ -1,40 +1,20 @@
-use utils::FileReader;
+use utils::{checked, Answer, FileReader, Solution};
 
-static DAY_03_FILE: &str = "./resources/aoc_25/day_03.txt";
+pub struct Day03;
 
-pub struct Day03Processor(String);
+impl Solution for Day03 {
+    type Input = Vec<BatteryBank>;
 
-impl Day03Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_03_FILE))
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        reader.parse_lines(BatteryBank::try_from)
     }
 
-    pub fn process(&self) {
-        PartOneProcessor::new(self.0.as_str()).process();
-        PartTwoProcessor::new(self.0.as_str()).process();
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::try_from(input.as_slice())?.get().into())
     }
-}
 
-impl Default for Day03Processor {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-struct PartOneProcessor(String);
-
-impl PartOneProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(file_reader) {
-            Ok(p1_value) => println!("AoC 25 Day 03 Part 1: {}", p1_value.get()),
-            Err(msg) => println!("AoC 25 Day 03 Part 1: Failed with message: {}", msg),
-        }
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::try_from(input.as_slice())?.get().into())
     }
 }
 
@@ -47,41 +27,11 @@ impl PartOneValue {
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
+impl TryFrom<&[BatteryBank]> for PartOneValue {
     type Error = crate::Error;
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let battery_banks = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(BatteryBank::try_from)
-            .collect::<Result<Vec<BatteryBank>, crate::Error>>()?;
-
-        let all_joltages: Vec<i8> = battery_banks
-            .into_iter()
-            .map(|bb| bb.max_joltage())
-            .collect();
-
-        let total_joltage = all_joltages.into_iter().map(|j| j as i64).sum();
-
-        Ok(PartOneValue(total_joltage))
-    }
-}
-
-struct PartTwoProcessor(String);
-
-impl PartTwoProcessor {
-    fn new(s: &str) -> Self {
-        Self(s.to_string())
-    }
-
-    fn process(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(file_reader) {
-            Ok(p2_value) => println!("AoC 25 Day 03 Part 2: {}", p2_value.get()),
-            Err(msg) => println!("AoC 25 Day 03 Part 2: Failed with message: {}", msg),
-        }
+    fn try_from(battery_banks: &[BatteryBank]) -> Result<Self, Self::Error> {
+        Ok(PartOneValue(total_joltage(battery_banks, 2)?))
     }
 }
 
@@ -94,70 +44,68 @@ impl PartTwoValue {
     }
 }
 
-impl TryFrom<FileReader> for PartTwoValue {
+impl TryFrom<&[BatteryBank]> for PartTwoValue {
     type Error = crate::Error;
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let battery_banks = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(BatteryBank::try_from)
-            .collect::<Result<Vec<BatteryBank>, crate::Error>>()?;
+    fn try_from(battery_banks: &[BatteryBank]) -> Result<Self, Self::Error> {
+        Ok(PartTwoValue(total_joltage(battery_banks, 12)?))
+    }
+}
 
-        let all_joltages: Vec<i64> = battery_banks
-            .into_iter()
-            .map(|bb| bb.overridden_joltage())
-            .collect();
+fn total_joltage(battery_banks: &[BatteryBank], batteries: usize) -> Result<i64, crate::Error> {
+    battery_banks.iter().try_fold(0, |total, bb| {
+        checked::add(total, bb.max_joltage_k(batteries)?.value, "total joltage")
+    })
+}
 
-        let total_joltage = all_joltages.into_iter().sum();
+#[derive(Debug, PartialEq)]
+pub struct BatteryBank(Vec<i8>);
 
-        Ok(PartTwoValue(total_joltage))
-    }
-}
+/// The batteries turned on in a bank, and the joltage they produce together.
 #[derive(Debug, PartialEq)]
-struct BatteryBank(Vec<i8>);
+pub struct Joltage {
+    pub value: i64,
+    /// The positions of the chosen batteries within the bank, in order.
+    pub batteries: Vec<usize>,
+}
 
 impl BatteryBank {
-    fn max_joltage(&self) -> i8 {
-        let mut battery_counter = 0;
-        let mut first_battery = 0i8;
-        let mut second_battery = 0i8;
-        let num_batteries = self.0.len();
-
-        for battery in self.0.iter() {
-            battery_counter += 1;
-
-            if battery > &first_battery && battery_counter < num_batteries {
-                first_battery = *battery;
-                second_battery = 0;
-                continue;
-            }
+    /// Picks the `k` batteries, kept in bank order, whose digits make the
+    /// largest joltage.
+    ///
+    /// Walks the bank once with a stack of chosen batteries, dropping any
+    /// smaller battery before a larger one while there are still enough
+    /// batteries left to make up `k`.
+    pub fn max_joltage_k(&self, k: usize) -> Result<Joltage, crate::Error> {
+        let Some(mut droppable) = self.0.len().checked_sub(k) else {
+            return Err(crate::Error::invalid_input(format!(
+                "expected at least {k} batteries in a bank of {}",
+                self.0.len()
+            )));
+        };
+        let mut chosen: Vec<usize> = Vec::with_capacity(self.0.len());
 
-            if battery > &second_battery {
-                second_battery = *battery
+        for (position, battery) in self.0.iter().enumerate() {
+            while droppable > 0 && chosen.last().is_some_and(|&last| self.0[last] < *battery) {
+                chosen.pop();
+                droppable -= 1;
             }
+
+            chosen.push(position);
         }
 
-        let Ok(joltage) =
-            (first_battery.to_string() + second_battery.to_string().as_str()).parse::<i8>()
-        else {
-            // This should be able to parse but return 0 if not.
-            return 0;
-        };
+        chosen.truncate(k);
 
-        joltage
-    }
+        let value = chosen.iter().try_fold(0i64, |value, &position| {
+            let shifted = checked::mul(value, 10, "joltage")?;
 
-    fn overridden_joltage(&self) -> i64 {
-        let mut tracker = JoltageTracker::new(12, self.0.len() as i8);
-        let mut battery_position = 0;
+            checked::add(shifted, self.0[position] as i64, "joltage")
+        })?;
 
-        for battery_value in self.0.clone().into_iter() {
-            battery_position += 1;
-            tracker.adjust_joltage(battery_value, battery_position);
-        }
-
-        tracker.joltage()
+        Ok(Joltage {
+            value,
+            batteries: chosen,
+        })
     }
 }
 
@@ -170,71 +118,24 @@ impl TryFrom<String> for BatteryBank {
             .collect::<Vec<char>>()
             .iter()
             .map(|x| x.to_string().parse::<i8>())
-            .collect::<Result<Vec<i8>, std::num::ParseIntError>>()?;
+            .collect::<Result<Vec<i8>, std::num::ParseIntError>>()
+            .map_err(|err| Self::Error::from(err).with_text(&value))?;
 
         Ok(Self(batteries))
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct JoltageTracker {
-    tracked_joltage: Vec<i8>,
-    joltage_size: i8,
-    possible_batteries: i8,
-}
-
-impl JoltageTracker {
-    fn new(joltage_size: i8, possible_batteries: i8) -> Self {
-        Self {
-            tracked_joltage: vec![],
-            joltage_size,
-            possible_batteries,
-        }
-    }
-
-    fn adjust_joltage(&mut self, battery_value: i8, battery_position: i8) {
-        let mut joltage_position: usize = 0;
-        let remaining_batteries = self.possible_batteries - battery_position;
-
-        for jolt in self.tracked_joltage.clone().into_iter() {
-            joltage_position += 1;
-
-            if battery_value > jolt
-                && remaining_batteries >= self.joltage_size - joltage_position as i8
-            {
-                self.tracked_joltage[joltage_position - 1] = battery_value;
-                self.tracked_joltage.truncate(joltage_position);
-                return;
-            }
-        }
-
-        if joltage_position < self.joltage_size as usize {
-            self.tracked_joltage.push(battery_value);
-        }
-    }
-
-    fn joltage(&self) -> i64 {
-        let Some(string_representation) = self
-            .tracked_joltage
-            .clone()
-            .into_iter()
-            .map(|j| j.to_string())
-            .reduce(|acc, s| acc + &s)
-        else {
-            // There should always be something here.
-            return 0;
-        };
-
-        string_representation.parse::<i64>().unwrap_or_default()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::Part;
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_03_sample.txt";
 
+    fn bank(batteries: &str) -> BatteryBank {
+        BatteryBank::try_from(String::from(batteries)).unwrap()
+    }
+
     #[test]
     fn test_battery_bank_from_string() {
         let batteries = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
@@ -247,98 +148,80 @@ mod tests {
 
     #[test]
     fn test_max_joltage() {
-        assert_eq!(
-            98,
-            BatteryBank::try_from(String::from("987654321111111"))
-                .unwrap()
-                .max_joltage()
-        );
-
-        assert_eq!(
-            89,
-            BatteryBank::try_from(String::from("811111111111119"))
-                .unwrap()
-                .max_joltage()
-        );
-
-        assert_eq!(
-            78,
-            BatteryBank::try_from(String::from("234234234234278"))
-                .unwrap()
-                .max_joltage()
-        );
-
-        assert_eq!(
-            92,
-            BatteryBank::try_from(String::from("818181911112111"))
-                .unwrap()
-                .max_joltage()
-        );
+        assert_eq!(98, bank("987654321111111").max_joltage_k(2).unwrap().value);
+        assert_eq!(89, bank("811111111111119").max_joltage_k(2).unwrap().value);
+        assert_eq!(78, bank("234234234234278").max_joltage_k(2).unwrap().value);
+        assert_eq!(92, bank("818181911112111").max_joltage_k(2).unwrap().value);
     }
 
     #[test]
     fn test_part_one_value_from_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
 
-        assert_eq!(357, PartOneValue::try_from(reader).unwrap().get())
+        assert_eq!(Answer::from(357), Day03::solve(reader, Part::One).unwrap())
     }
 
     #[test]
-    fn test_adjust_joltage1() {
-        let mut tracker = JoltageTracker::new(12, 15);
-        let batteries = vec![2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8];
-
-        let mut position = 0;
-        for battery in batteries.into_iter() {
-            position += 1;
-            tracker.adjust_joltage(battery, position);
-        }
-
-        let expected = JoltageTracker {
-            tracked_joltage: vec![4, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8],
-            joltage_size: 12,
-            possible_batteries: 15,
+    fn test_max_joltage_k1() {
+        let expected = Joltage {
+            value: 434234234278,
+            batteries: vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
         };
 
-        assert_eq!(expected, tracker)
+        assert_eq!(expected, bank("234234234234278").max_joltage_k(12).unwrap())
     }
 
     #[test]
-    fn test_adjust_joltage2() {
-        let mut tracker = JoltageTracker::new(12, 15);
-        let batteries = vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1];
-
-        let mut position = 0;
-        for battery in batteries.into_iter() {
-            position += 1;
-            tracker.adjust_joltage(battery, position);
-        }
-
-        let expected = JoltageTracker {
-            tracked_joltage: vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1],
-            joltage_size: 12,
-            possible_batteries: 15,
+    fn test_max_joltage_k2() {
+        let expected = Joltage {
+            value: 987654321111,
+            batteries: (0..12).collect(),
         };
 
-        assert_eq!(expected, tracker)
+        assert_eq!(expected, bank("987654321111111").max_joltage_k(12).unwrap())
     }
 
     #[test]
-    fn test_joltage() {
-        let tracker = JoltageTracker {
-            tracked_joltage: vec![4, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8],
-            joltage_size: 12,
-            possible_batteries: 15,
-        };
+    fn test_max_joltage_k_edges() {
+        let bank = bank("818181911112111");
 
-        assert_eq!(434234234278, tracker.joltage())
+        assert_eq!(0, bank.max_joltage_k(0).unwrap().value);
+        assert_eq!(vec![6], bank.max_joltage_k(1).unwrap().batteries);
+        assert_eq!(818181911112111, bank.max_joltage_k(15).unwrap().value);
+        assert_eq!(
+            "expected at least 16 batteries in a bank of 15",
+            bank.max_joltage_k(16).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_max_joltage_k_overflow() {
+        let bank = bank(&"9".repeat(20));
+
+        assert_eq!(999999999999999999, bank.max_joltage_k(18).unwrap().value);
+        assert_eq!(
+            "joltage overflowed",
+            bank.max_joltage_k(19).unwrap_err().to_string()
+        );
     }
 
     #[test]
     fn test_part_two_value_from_file_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
-        let p2_value = PartTwoValue::try_from(reader);
+        let p2_value = Day03::solve(reader, Part::Two);
+
+        assert_eq!(Answer::from(3121910778619_i64), p2_value.unwrap())
+    }
+
+    #[test]
+    fn test_max_joltage_k_long_bank() {
+        let bank = BatteryBank::try_from("1".repeat(200) + "987654321").unwrap();
+        let joltage = bank.max_joltage_k(12).unwrap();
 
-        assert_eq!(3121910778619, p2_value.unwrap().get())
+        assert_eq!(111987654321, joltage.value);
+        assert_eq!(
+            vec![0, 1, 2, 200, 201, 202, 203, 204, 205, 206, 207, 208],
+            joltage.batteries
+        );
     }
 }
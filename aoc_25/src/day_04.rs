@@ -1,158 +1,209 @@
-use utils::FileReader;
+use itertools::Either;
+use utils::{Answer, FileReader, Grid, Point2, Solution};
 
-static DAY_04_FILE: &str = "./resources/aoc_25/day_04.txt";
+pub struct Day04;
 
-pub struct Day04Processor(String);
+impl Solution for Day04 {
+    type Input = PaperRollGrid;
 
-impl Day04Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_04_FILE))
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        PaperRollGrid::try_from(reader)
     }
 
-    pub fn process(&self) {
-        self.process_part1();
-        self.process_part2();
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(input.removable_rolls().into())
     }
 
-    fn process_part1(&self) {
-        let file_reader = FileReader::new(&self.0);
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        // Removing rolls changes the grid, so part two works on its own copy.
+        let mut grid = input.clone();
 
-        match PaperRollGrid::try_from(file_reader) {
-            Ok(grid) => println!("AoC 25 Day 04 Part 1: {}", grid.removable_rolls()),
-            Err(msg) => println!("AoC 25 Day 04 Part 1: Failed with message: {}", msg),
-        }
+        Ok(grid.remove_all().into())
     }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaperRollGrid {
+    grid: Grid<char>,
+}
 
-    fn process_part2(&self) {
-        let file_reader = FileReader::new(&self.0);
+/// Which of the cells around a roll count as its neighbors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Neighborhood {
+    /// The four cells above, below, left and right.
+    Orthogonal,
+    /// All eight cells around, diagonals included.
+    Surrounding,
+}
 
-        match PaperRollGrid::try_from(file_reader) {
-            Ok(mut grid) => println!("AoC 25 Day 04 Part 2: {}", grid.remove_all()),
-            Err(msg) => println!("AoC 25 Day 04 Part 2: Failed with message: {}", msg),
+impl Neighborhood {
+    // The neighbors of `point` that are on the grid.
+    fn of<'a, T>(self, grid: &'a Grid<T>, point: Point2) -> impl Iterator<Item = Point2> + 'a {
+        match self {
+            Neighborhood::Orthogonal => Either::Left(grid.point_neighbors4(point)),
+            Neighborhood::Surrounding => Either::Right(grid.point_neighbors8(point)),
         }
     }
 }
 
-impl Default for Day04Processor {
+/// A roll can be removed once fewer than `threshold` of its neighbors are
+/// rolls.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RemovalRule {
+    pub threshold: usize,
+    pub neighborhood: Neighborhood,
+}
+
+impl Default for RemovalRule {
     fn default() -> Self {
-        Self::new()
+        Self {
+            threshold: 4,
+            neighborhood: Neighborhood::Surrounding,
+        }
     }
 }
 
+/// The order rolls come out of the grid in. Wave 1 is every roll that can be
+/// removed straight away, and each wave after that is every roll freed up by
+/// removing the one before.
 #[derive(Debug, PartialEq)]
-struct PaperRollGrid {
-    // The use of a buffered grid is for convenience. I build a buffer around the whole grid
-    // so that I don't have to worry about any index out of bounds errors. If I'm looking at
-    // a specific position and it's a BUFFERED_CHAR, I can just skip over it.
-    buffered_grid: Vec<Vec<char>>,
+pub struct RemovalWaves {
+    removed_in: Grid<Option<usize>>,
+    wave_sizes: Vec<usize>,
 }
 
-impl PaperRollGrid {
-    const BUFFER_CHAR: char = '=';
-    const PAPER_ROLL: char = '@';
+impl RemovalWaves {
+    /// The wave the roll at `point` was removed in, if it ever was. As with
+    /// `Grid::get_point`, `x` is the column and `y` is the row.
+    pub fn wave_of(&self, point: Point2) -> Option<usize> {
+        self.removed_in.get_point(point).copied().flatten()
+    }
 
-    fn new(size: usize) -> Self {
-        // Create the first and last buffer rows
-        let buffer_row: Vec<char> = vec![PaperRollGrid::BUFFER_CHAR; size + 2];
-        let buffered_grid: Vec<Vec<char>> = vec![buffer_row.clone(), buffer_row];
+    pub fn wave_count(&self) -> usize {
+        self.wave_sizes.len()
+    }
 
-        Self { buffered_grid }
+    /// The number of rolls removed in wave `wave`, counting from 1.
+    pub fn rolls_in_wave(&self, wave: usize) -> usize {
+        wave.checked_sub(1)
+            .and_then(|index| self.wave_sizes.get(index))
+            .copied()
+            .unwrap_or_default()
     }
 
-    fn add_row(&mut self, row: &str) {
-        let current_size = self.buffered_grid.len();
-        let buffered_row =
-            PaperRollGrid::BUFFER_CHAR.to_string() + row + &PaperRollGrid::BUFFER_CHAR.to_string();
+    pub fn total_rolls(&self) -> usize {
+        self.wave_sizes.iter().sum()
+    }
 
-        self.buffered_grid.insert(
-            current_size - 1,
-            buffered_row.chars().collect::<Vec<char>>(),
-        )
+    fn removed_rolls(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.removed_in
+            .iter()
+            .filter(|(_, wave)| wave.is_some())
+            .map(|(position, _)| Point2::from_position(position))
     }
+}
+
+impl PaperRollGrid {
+    const PAPER_ROLL: char = '@';
+    const REMOVED_ROLL: char = 'X';
 
-    // Makes multiple passes through the paper rolls, removing any removable rolls each pass.
-    // Returns the total number of rolls removed after no more rolls can be removed.
+    // Removes every roll that can eventually be removed under the default rule.
+    // Returns the total number of rolls removed.
     fn remove_all(&mut self) -> i64 {
-        let mut removed_rolls: i64 = 0;
-
-        loop {
-            let mut removable_rolls: Vec<Position> = vec![];
-
-            // For simplicity sake, we can skip the first and last
-            // rows of the grid since they're buffer rows.
-            for row in 1..self.buffered_grid.len() {
-                for column in 1..self.buffered_grid[row].len() {
-                    let this_position = Position(row, column);
-                    if self.buffered_grid[row][column] == PaperRollGrid::PAPER_ROLL
-                        && self.is_removable(this_position)
+        let waves = self.removal_waves(RemovalRule::default());
+        self.remove_rolls(waves.removed_rolls());
+
+        waves.total_rolls() as i64
+    }
+
+    /// Works out which wave every roll is removed in.
+    ///
+    /// Each roll keeps a count of its neighboring rolls, and removing a roll
+    /// only touches the counts around it. A roll joins the next wave as soon
+    /// as its count drops below the threshold, so the grid is never rescanned.
+    pub fn removal_waves(&self, rule: RemovalRule) -> RemovalWaves {
+        let mut neighbor_counts: Grid<usize> = Grid::new(self.grid.width(), self.grid.height(), 0);
+        let mut removed_in: Grid<Option<usize>> =
+            Grid::new(self.grid.width(), self.grid.height(), None);
+
+        for position in self.grid.find_all(&PaperRollGrid::PAPER_ROLL) {
+            neighbor_counts[position] =
+                self.paper_rolls_around(Point2::from_position(position), rule);
+        }
+
+        let mut wave: Vec<Point2> = self.removable_rolls_at(rule).collect();
+        let mut wave_sizes = vec![];
+
+        while !wave.is_empty() {
+            let wave_number = wave_sizes.len() + 1;
+            for roll in wave.iter() {
+                if let Some(removed) = removed_in.get_point_mut(*roll) {
+                    *removed = Some(wave_number);
+                }
+            }
+
+            let mut next_wave = vec![];
+            for roll in wave.iter() {
+                for neighbor in rule.neighborhood.of(&self.grid, *roll) {
+                    if !self.is_paper_roll(neighbor)
+                        || removed_in.get_point(neighbor).is_some_and(Option::is_some)
                     {
-                        removable_rolls.push(this_position);
+                        continue;
+                    }
+
+                    // Only the removal that takes it under the threshold queues it.
+                    if let Some(count) = neighbor_counts.get_point_mut(neighbor) {
+                        *count -= 1;
+                        if *count + 1 == rule.threshold {
+                            next_wave.push(neighbor);
+                        }
                     }
                 }
             }
 
-            if removable_rolls.is_empty() {
-                return removed_rolls;
-            } else {
-                removed_rolls += removable_rolls.len() as i64;
-                self.remove_rolls(removable_rolls);
-            }
+            wave_sizes.push(wave.len());
+            wave = next_wave;
+        }
+
+        RemovalWaves {
+            removed_in,
+            wave_sizes,
         }
     }
 
     fn removable_rolls(&self) -> i64 {
-        let mut removable_rolls: i64 = 0;
-
-        // For simplicity sake, we can skip the first and last
-        // rows of the grid since they're buffer rows.
-        for row in 1..self.buffered_grid.len() {
-            for column in 1..self.buffered_grid[row].len() {
-                if self.buffered_grid[row][column] == PaperRollGrid::PAPER_ROLL
-                    && self.is_removable(Position(row, column))
-                {
-                    removable_rolls += 1;
-                }
-            }
-        }
+        self.removable_rolls_at(RemovalRule::default()).count() as i64
+    }
 
-        removable_rolls
+    fn removable_rolls_at(&self, rule: RemovalRule) -> impl Iterator<Item = Point2> + '_ {
+        self.grid
+            .find_all(&PaperRollGrid::PAPER_ROLL)
+            .map(Point2::from_position)
+            .filter(move |roll| self.is_removable(*roll, rule))
     }
 
-    // Removes rolls by placing an 'X' at each specified position
-    fn remove_rolls(&mut self, roll_positions: Vec<Position>) {
-        for roll in roll_positions {
-            self.buffered_grid[roll.row()][roll.column()] = 'X';
+    // Removes rolls by placing an 'X' at each specified point
+    fn remove_rolls(&mut self, rolls: impl Iterator<Item = Point2>) {
+        for roll in rolls {
+            if let Some(cell) = self.grid.get_point_mut(roll) {
+                *cell = PaperRollGrid::REMOVED_ROLL;
+            }
         }
     }
 
-    fn is_removable(&self, position: Position) -> bool {
-        let neighbors = self.neighbors(position);
-        let paper_rolls = neighbors
-            .into_iter()
-            .filter(|pr| *pr == PaperRollGrid::PAPER_ROLL)
-            .collect::<Vec<char>>()
-            .len();
-
-        paper_rolls < 4
+    fn is_paper_roll(&self, point: Point2) -> bool {
+        self.grid.get_point(point) == Some(&PaperRollGrid::PAPER_ROLL)
     }
 
-    fn neighbors(&self, position: Position) -> Vec<char> {
-        let mut neighbors: Vec<char> = vec![];
-
-        // If this position is a buffered character, no point in checking.
-        if self.buffered_grid[position.row()][position.column()] == PaperRollGrid::BUFFER_CHAR {
-            return neighbors;
-        }
-
-        for row in position.row() - 1..position.row() + 2 {
-            for column in position.column() - 1..position.column() + 2 {
-                if !(row == position.row() && column == position.column()) {
-                    neighbors.push(self.buffered_grid[row][column])
-                }
-            }
-        }
+    fn is_removable(&self, point: Point2, rule: RemovalRule) -> bool {
+        self.paper_rolls_around(point, rule) < rule.threshold
+    }
 
-        neighbors
+    fn paper_rolls_around(&self, point: Point2, rule: RemovalRule) -> usize {
+        rule.neighborhood
+            .of(&self.grid, point)
+            .filter(|neighbor| self.is_paper_roll(*neighbor))
+            .count()
     }
 }
 
@@ -160,30 +211,15 @@ impl TryFrom<FileReader> for PaperRollGrid {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_lines = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .collect::<Vec<String>>();
-
-        let mut grid = PaperRollGrid::new(input_lines[0].len());
-        for grid_row in input_lines {
-            grid.add_row(&grid_row);
-        }
-
-        Ok(grid)
-    }
-}
+        let grid = reader.read_grid()?;
 
-#[derive(Copy, Clone)]
-struct Position(usize, usize);
-
-impl Position {
-    fn row(&self) -> usize {
-        self.0
-    }
+        if grid.is_empty() {
+            return Err(Self::Error::invalid_input(
+                "expected at least one row of paper rolls",
+            ));
+        }
 
-    fn column(&self) -> usize {
-        self.1
+        Ok(Self { grid })
     }
 }
 
@@ -193,68 +229,20 @@ mod tests {
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_04_sample.txt";
 
-    fn create_buffered_row(size: usize) -> Vec<char> {
-        vec![PaperRollGrid::BUFFER_CHAR; size]
-    }
-
     #[test]
-    fn test_new_paper_roll_grid() {
-        let buffered_row = create_buffered_row(6);
-        let initial_buffer: Vec<Vec<char>> = vec![buffered_row.clone(), buffered_row];
-        let expected = PaperRollGrid {
-            buffered_grid: initial_buffer,
-        };
-
-        assert_eq!(expected, PaperRollGrid::new(4));
-    }
+    fn test_is_removable() {
+        // The grid:
+        // @ @ @
+        // @ @ .
+        // @ . @
+        let grid = PaperRollGrid::try_from(FileReader::from_text("@@@\n@@.\n@.@\n")).unwrap();
 
-    #[test]
-    fn test_add_row_to_paper_roll_grid() {
-        let buffered_row = create_buffered_row(6);
-        let added_row = vec![
-            PaperRollGrid::BUFFER_CHAR,
-            'b',
-            'l',
-            'a',
-            'h',
-            PaperRollGrid::BUFFER_CHAR,
-        ];
-        let expected = PaperRollGrid {
-            buffered_grid: vec![buffered_row.clone(), added_row, buffered_row],
-        };
-
-        let mut paper_roll_grid = PaperRollGrid::new(4);
-        paper_roll_grid.add_row("blah");
-
-        assert_eq!(expected, paper_roll_grid);
-    }
+        let rule = RemovalRule::default();
 
-    #[test]
-    fn test_neighbors() {
-        // The grid:
-        // = = = = = = =
-        // = a b c d e =
-        // = 1 2 3 4 5 =
-        // = f g h i j =
-        // = 6 7 8 9 0 =
-        // = = = = = = =
-
-        let mut grid = PaperRollGrid::new(5);
-        grid.add_row("abcde");
-        grid.add_row("12345");
-        grid.add_row("fghij");
-        grid.add_row("67890");
-
-        let expected1 = vec!['b', 'c', 'd', '2', '4', 'g', 'h', 'i']; // position 2,3
-        let expected2 = vec!['h', 'i', 'j', '8', '0', '=', '=', '=']; // position 4,4
-
-        // Any buffer position should return no neighbors
-        assert!(grid.neighbors(Position(0, 0)).is_empty());
-        assert!(grid.neighbors(Position(5, 3)).is_empty());
-        assert!(grid.neighbors(Position(2, 6)).is_empty());
-
-        assert_eq!(expected1, grid.neighbors(Position(2, 3)));
-        assert_eq!(expected2, grid.neighbors(Position(4, 4)));
+        assert!(grid.is_removable(Point2::new(0, 0), rule));
+        assert!(!grid.is_removable(Point2::new(0, 1), rule));
+        assert!(!grid.is_removable(Point2::new(1, 1), rule));
+        assert!(grid.is_removable(Point2::new(2, 2), rule));
     }
 
     #[test]
@@ -272,4 +260,95 @@ mod tests {
 
         assert_eq!(43, grid.remove_all());
     }
+
+    #[test]
+    fn test_removal_waves() {
+        let grid = PaperRollGrid::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
+        let waves = grid.removal_waves(RemovalRule::default());
+
+        assert_eq!(13, waves.rolls_in_wave(1));
+        assert_eq!(43, waves.total_rolls());
+        assert_eq!(0, waves.rolls_in_wave(0));
+        assert_eq!(0, waves.rolls_in_wave(waves.wave_count() + 1));
+        assert_eq!(Some(1), waves.wave_of(Point2::new(2, 0)));
+        assert_eq!(None, waves.wave_of(Point2::new(0, 0)));
+        assert_eq!(None, waves.wave_of(Point2::new(-1, 0)));
+    }
+
+    #[test]
+    fn test_removal_waves_match_passes() {
+        // Removing each pass's removable rolls in turn gives the same waves.
+        for rule in [
+            RemovalRule::default(),
+            RemovalRule {
+                threshold: 3,
+                neighborhood: Neighborhood::Orthogonal,
+            },
+            RemovalRule {
+                threshold: 6,
+                neighborhood: Neighborhood::Surrounding,
+            },
+        ] {
+            let mut grid = PaperRollGrid::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
+            let waves = grid.removal_waves(rule);
+            let mut wave = 0;
+
+            loop {
+                let removable: Vec<Point2> = grid.removable_rolls_at(rule).collect();
+                if removable.is_empty() {
+                    break;
+                }
+
+                wave += 1;
+                assert_eq!(removable.len(), waves.rolls_in_wave(wave));
+                assert!(removable.iter().all(|p| waves.wave_of(*p) == Some(wave)));
+
+                grid.remove_rolls(removable.into_iter());
+            }
+
+            assert_eq!(wave, waves.wave_count());
+        }
+    }
+
+    #[test]
+    fn test_orthogonal_waves() {
+        // The grid:
+        // @ @ @
+        // @ @ @
+        // @ @ @
+        let grid = PaperRollGrid::try_from(FileReader::from_text("@@@\n@@@\n@@@\n")).unwrap();
+        let waves = grid.removal_waves(RemovalRule {
+            threshold: 3,
+            neighborhood: Neighborhood::Orthogonal,
+        });
+
+        assert_eq!(3, waves.wave_count());
+        assert_eq!(
+            vec![4, 4, 1],
+            (1..=3)
+                .map(|wave| waves.rolls_in_wave(wave))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(3), waves.wave_of(Point2::new(1, 1)));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let reader = FileReader::from_text("");
+
+        assert_eq!(
+            "expected at least one row of paper rolls",
+            PaperRollGrid::try_from(reader).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_ragged_grid() {
+        let reader = FileReader::from_text("@@.\n@@@@\n@.@\n");
+
+        assert_eq!(
+            "line 2: expected every row to be 3 wide",
+            PaperRollGrid::try_from(reader).unwrap_err().to_string()
+        );
+    }
 }
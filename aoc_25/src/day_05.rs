@@ -1,49 +1,27 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::ops::RangeInclusive;
-use utils::FileReader;
+use utils::{Answer, FileReader, RangeSet, Solution};
 
-static DAY_05_FILE: &str = "./resources/aoc_25/day_05.txt";
 static FRESH_ID_RANGE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^(?<start>\d+)-(?<end>\d+)$").expect("Expected a valid regex for fresh id ranges")
 });
-static INGREDIENT_ID_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^(?<id>\d+)$").expect("Expected a valid regex for ingredient id"));
 
-pub struct Day05Processor(String);
+pub struct Day05;
 
-impl Day05Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_05_FILE))
-    }
-
-    pub fn process(&self) {
-        self.process_part1();
-        self.process_part2();
-    }
+impl Solution for Day05 {
+    type Input = Day05Input;
 
-    fn process_part1(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(file_reader) {
-            Ok(p1_value) => println!("AoC 25 Day 05 Part 1: {}", p1_value.get()),
-            Err(msg) => println!("AoC 25 Day 05 Part 1: Failed with message: {}", msg),
-        }
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        Day05Input::try_from(reader)
     }
 
-    fn process_part2(&self) {
-        let file_reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(file_reader) {
-            Ok(p2_value) => println!("AoC 25 Day 05 Part 2: {}", p2_value.get()),
-            Err(msg) => println!("AoC 25 Day 05 Part 2: Failed with message: {}", msg),
-        }
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::from(input).get().into())
     }
-}
 
-impl Default for Day05Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::try_from(input)?.get().into())
     }
 }
 
@@ -63,171 +41,262 @@ impl PartTwoValue {
     }
 }
 
+/// The fresh id ranges as listed, along with the same ranges merged into a
+/// [`RangeSet`] for looking ids up.
 #[derive(Debug, PartialEq)]
-struct FreshIdRanges(Vec<RangeInclusive<i64>>);
+pub struct FreshIdRanges {
+    ranges: Vec<RangeInclusive<i64>>,
+    merged: RangeSet<i64>,
+    // The listed ranges that hold anything, ordered by start, and laid out as
+    // an implicit interval tree: the middle of any span of `by_start` is the
+    // root of that span's subtree, and `max_end` holds the largest end within
+    // the subtree rooted there.
+    by_start: Vec<usize>,
+    max_end: Vec<i64>,
+}
 
-impl FreshIdRanges {
-    fn new() -> Self {
-        let empty_ranges: Vec<RangeInclusive<i64>> = vec![];
-        Self(empty_ranges)
+/// Whether an ingredient id is fresh, and which listed ranges say so.
+#[derive(Debug, PartialEq)]
+pub struct IdClassification {
+    pub id: i64,
+    /// The indexes of the listed ranges holding the id, in listed order.
+    pub ranges: Vec<usize>,
+}
+
+impl IdClassification {
+    pub fn is_fresh(&self) -> bool {
+        !self.ranges.is_empty()
     }
+}
 
-    fn ranges(&self) -> &Vec<RangeInclusive<i64>> {
-        &self.0
+impl FreshIdRanges {
+    pub fn new(ranges: Vec<RangeInclusive<i64>>) -> Self {
+        let merged: RangeSet<i64> = ranges.iter().cloned().collect();
+
+        let mut by_start: Vec<usize> = (0..ranges.len())
+            .filter(|&index| !ranges[index].is_empty())
+            .collect();
+        by_start.sort_by_key(|&index| *ranges[index].start());
+
+        let mut fresh_ranges = Self {
+            ranges,
+            merged,
+            max_end: vec![i64::MIN; by_start.len()],
+            by_start,
+        };
+        fresh_ranges.fill_max_end(0, fresh_ranges.by_start.len());
+
+        fresh_ranges
     }
 
-    fn is_fresh_id_range(possible_range: String) -> bool {
-        FRESH_ID_RANGE_RE.is_match(&possible_range)
+    // Fills in `max_end` for the subtree covering `by_start[low..high]`,
+    // returning the largest end within it.
+    fn fill_max_end(&mut self, low: usize, high: usize) -> i64 {
+        if low >= high {
+            return i64::MIN;
+        }
+
+        let middle = low + (high - low) / 2;
+        let end = *self.ranges[self.by_start[middle]].end();
+        let max_end = end
+            .max(self.fill_max_end(low, middle))
+            .max(self.fill_max_end(middle + 1, high));
+        self.max_end[middle] = max_end;
+
+        max_end
     }
 
-    fn fresh_id_count(&self) -> i64 {
-        let mut ranges = self.0.clone();
-        ranges.sort_by(|a, b| a.start().cmp(b.start()));
+    fn parse_range(range: String) -> Result<RangeInclusive<i64>, crate::Error> {
+        let Some(range_data) = FRESH_ID_RANGE_RE.captures(&range) else {
+            return Err(crate::Error::invalid_input("expected a range like 3-5").with_text(range));
+        };
 
-        let mut total: i64 = 0;
-        let mut current_end: i64 = 0;
+        let parse_id = |id: &str| {
+            id.parse::<i64>()
+                .map_err(|err| crate::Error::from(err).with_text(id))
+        };
 
-        for range in ranges {
-            if *range.end() <= current_end {
-                continue;
-            }
+        Ok(parse_id(&range_data["start"])?..=parse_id(&range_data["end"])?)
+    }
+
+    /// The fresh ids as sorted ranges, with any that overlap or touch merged.
+    pub fn merged(&self) -> &RangeSet<i64> {
+        &self.merged
+    }
 
-            let start = if *range.start() <= current_end {
-                current_end + 1
-            } else {
-                *range.start()
-            };
+    /// Classifies each id in turn. Ids outside every range are ruled out by a
+    /// binary search over the merged ranges, and the interval tree only visits
+    /// subtrees that hold a match, so the cost of an id grows with the number
+    /// of ranges holding it rather than the number listed.
+    pub fn classify(&self, ingredient_ids: &[i64]) -> Vec<IdClassification> {
+        ingredient_ids
+            .iter()
+            .map(|&id| IdClassification {
+                id,
+                ranges: self.ranges_containing(id),
+            })
+            .collect()
+    }
 
-            let adjust_amount = *range.end() - start + 1;
+    fn ranges_containing(&self, ingredient_id: i64) -> Vec<usize> {
+        let mut containing = vec![];
 
-            total += adjust_amount;
-            current_end = *range.end();
+        if self.is_fresh_ingredient(ingredient_id) {
+            self.collect_containing(ingredient_id, 0, self.by_start.len(), &mut containing);
+            containing.sort();
         }
 
-        total
+        containing
     }
 
-    fn add_range(&mut self, range: String) {
-        let Some(range_data) = FRESH_ID_RANGE_RE.captures(&range) else {
+    fn collect_containing(
+        &self,
+        ingredient_id: i64,
+        low: usize,
+        high: usize,
+        containing: &mut Vec<usize>,
+    ) {
+        if low >= high {
             return;
-        };
-
-        let Ok(start) = range_data["start"].parse::<i64>() else {
-            return;
-        };
+        }
 
-        let Ok(end) = range_data["end"].parse::<i64>() else {
+        // Nothing in this subtree reaches the id.
+        let middle = low + (high - low) / 2;
+        if self.max_end[middle] < ingredient_id {
             return;
-        };
+        }
 
-        self.0.push(start..=end)
-    }
+        self.collect_containing(ingredient_id, low, middle, containing);
 
-    // Checks through all the fresh id ranges to see if this ingredient id
-    // is contained.
-    fn is_fresh_ingredient(&self, ingredient_id: i64) -> bool {
-        for range in self.ranges().iter() {
-            if range.contains(&ingredient_id) {
-                return true;
+        // Everything right of the middle starts no earlier than it does.
+        let range = &self.ranges[self.by_start[middle]];
+        if *range.start() <= ingredient_id {
+            if *range.end() >= ingredient_id {
+                containing.push(self.by_start[middle]);
             }
+
+            self.collect_containing(ingredient_id, middle + 1, high, containing);
         }
+    }
+
+    fn fresh_id_count(&self) -> Result<i64, crate::Error> {
+        self.merged.len()
+    }
 
-        false
+    fn is_fresh_ingredient(&self, ingredient_id: i64) -> bool {
+        self.merged.contains(&ingredient_id)
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct IngredientIDs;
+pub struct Day05Input {
+    fresh_ranges: FreshIdRanges,
+    ingredient_ids: Vec<i64>,
+}
 
-impl IngredientIDs {
-    fn is_ingredient_id(possible_ingredient_id: String) -> bool {
-        INGREDIENT_ID_RE.is_match(&possible_ingredient_id)
+impl Day05Input {
+    pub fn fresh_ranges(&self) -> &FreshIdRanges {
+        &self.fresh_ranges
+    }
+
+    pub fn ingredient_ids(&self) -> &[i64] {
+        &self.ingredient_ids
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
+impl TryFrom<FileReader> for Day05Input {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_lines = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .collect::<Vec<String>>();
-
-        let mut fresh_ranges = FreshIdRanges::new();
-        let mut fresh_ingredients: i64 = 0;
-
-        for input in input_lines.into_iter() {
-            if FreshIdRanges::is_fresh_id_range(input.clone()) {
-                fresh_ranges.add_range(input.clone());
-            } else if IngredientIDs::is_ingredient_id(input.clone()) {
-                if let Ok(ingredient) = input.parse::<i64>() {
-                    if fresh_ranges.is_fresh_ingredient(ingredient) {
-                        fresh_ingredients += 1;
-                    }
-                }
-            }
-        }
+        let sections = reader.read_sections()?;
+
+        let [ranges, ids] = &sections[..] else {
+            return Err(Self::Error::invalid_input(
+                "expected fresh id ranges and ingredient ids separated by a blank line",
+            ));
+        };
 
-        Ok(PartOneValue(fresh_ingredients))
+        let fresh_ranges = FreshIdRanges::new(ranges.parse_lines(FreshIdRanges::parse_range)?);
+        let ingredient_ids = ids.parse_lines(|id| {
+            id.parse::<i64>()
+                .map_err(|err| Self::Error::from(err).with_text(&id))
+        })?;
+
+        Ok(Self {
+            fresh_ranges,
+            ingredient_ids,
+        })
     }
 }
 
-impl TryFrom<FileReader> for PartTwoValue {
-    type Error = crate::Error;
+impl From<&Day05Input> for PartOneValue {
+    fn from(input: &Day05Input) -> Self {
+        let fresh_ingredients = input
+            .ingredient_ids
+            .iter()
+            .filter(|id| input.fresh_ranges.is_fresh_ingredient(**id))
+            .count();
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_lines = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .collect::<Vec<String>>();
-
-        let mut fresh_ranges = FreshIdRanges::new();
+        PartOneValue(fresh_ingredients as i64)
+    }
+}
 
-        for input in input_lines.into_iter() {
-            if FreshIdRanges::is_fresh_id_range(input.clone()) {
-                fresh_ranges.add_range(input.clone());
-            }
-        }
+impl TryFrom<&Day05Input> for PartTwoValue {
+    type Error = crate::Error;
 
-        Ok(PartTwoValue(fresh_ranges.fresh_id_count()))
+    fn try_from(input: &Day05Input) -> Result<Self, Self::Error> {
+        Ok(PartTwoValue(input.fresh_ranges.fresh_id_count()?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::Part;
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_05_sample.txt";
 
     #[test]
-    fn test_is_fresh_id_range_match() {
-        assert!(FreshIdRanges::is_fresh_id_range(String::from("15-30")));
-        assert!(!FreshIdRanges::is_fresh_id_range(String::from("77")));
-        assert!(!FreshIdRanges::is_fresh_id_range(String::from("")));
+    fn test_parse_range() {
+        assert_eq!(
+            5..=30,
+            FreshIdRanges::parse_range(String::from("5-30")).unwrap()
+        );
+        assert!(FreshIdRanges::parse_range(String::from("77")).is_err());
+        assert!(FreshIdRanges::parse_range(String::from("")).is_err());
     }
 
     #[test]
-    fn test_add_range() {
-        let expected = FreshIdRanges(vec![5..=30]);
-        let mut fresh_id_ranges = FreshIdRanges::new();
-        fresh_id_ranges.add_range(String::from("5-30"));
+    fn test_input_from_reader() {
+        let expected = Day05Input {
+            fresh_ranges: FreshIdRanges::new(vec![3..=5, 10..=14, 16..=20, 12..=18]),
+            ingredient_ids: vec![1, 5, 8, 11, 17, 32],
+        };
 
-        assert_eq!(expected, fresh_id_ranges);
+        assert_eq!(
+            expected,
+            Day05Input::try_from(FileReader::new(SAMPLE_FILE)).unwrap()
+        );
     }
 
     #[test]
-    fn test_is_ingredient_id() {
-        assert!(IngredientIDs::is_ingredient_id(String::from("72")));
-        assert!(!IngredientIDs::is_ingredient_id(String::from("")));
-        assert!(!IngredientIDs::is_ingredient_id(String::from("5-30")));
+    fn test_invalid_sections() {
+        let misplaced_id = Day05Input::try_from(FileReader::from_text("3-5\n7\n\n1\n"));
+        let missing_ids = Day05Input::try_from(FileReader::from_text("3-5\n10-14\n"));
+
+        assert_eq!(
+            "line 2: expected a range like 3-5, got \"7\"",
+            misplaced_id.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "expected fresh id ranges and ingredient ids separated by a blank line",
+            missing_ids.unwrap_err().to_string()
+        );
     }
 
     #[test]
     fn test_is_fresh_ingredient() {
-        let mut fresh_id_ranges = FreshIdRanges::new();
-        fresh_id_ranges.add_range(String::from("100-125"));
-        fresh_id_ranges.add_range(String::from("150-175"));
+        let fresh_id_ranges = FreshIdRanges::new(vec![100..=125, 150..=175]);
 
         // Fresh Ingredients
         assert!(fresh_id_ranges.is_fresh_ingredient(100));
@@ -245,17 +314,92 @@ mod tests {
         assert!(!fresh_id_ranges.is_fresh_ingredient(200));
     }
 
+    #[test]
+    fn test_classify() {
+        let input = Day05Input::try_from(FileReader::new(SAMPLE_FILE)).unwrap();
+        let classified = input.fresh_ranges().classify(input.ingredient_ids());
+
+        assert_eq!(
+            vec![
+                (1, vec![]),
+                (5, vec![0]),
+                (8, vec![]),
+                (11, vec![1]),
+                (17, vec![2, 3]),
+                (32, vec![]),
+            ],
+            classified
+                .iter()
+                .map(|c| (c.id, c.ranges.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, classified.iter().filter(|c| c.is_fresh()).count());
+        assert_eq!(
+            vec![3..=5, 10..=20],
+            input
+                .fresh_ranges()
+                .merged()
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_classify_nested_ranges() {
+        let fresh_id_ranges = FreshIdRanges::new(vec![
+            20..=30,
+            0..=100,
+            RangeInclusive::new(50, 40),
+            25..=25,
+            101..=110,
+        ]);
+        let classified = fresh_id_ranges.classify(&[25, 29, 31, 105, 111]);
+
+        assert_eq!(
+            vec![vec![0, 1, 3], vec![0, 1], vec![1], vec![4], vec![]],
+            classified.into_iter().map(|c| c.ranges).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_classify_matches_scan() {
+        // Lots of nested and overlapping ranges, checked against a plain scan.
+        let ranges: Vec<RangeInclusive<i64>> = (0..200)
+            .map(|i| {
+                let start = (i * 37) % 101;
+                start..=start + (i * 53) % 61 - 10
+            })
+            .collect();
+        let fresh_id_ranges = FreshIdRanges::new(ranges.clone());
+
+        for id in -5..=170 {
+            let expected: Vec<usize> = (0..ranges.len())
+                .filter(|&index| ranges[index].contains(&id))
+                .collect();
+
+            assert_eq!(expected, fresh_id_ranges.ranges_containing(id), "id {id}");
+        }
+    }
+
     #[test]
     fn test_part_one_value_from_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
 
-        assert_eq!(3, PartOneValue::try_from(reader).unwrap().get());
+        assert_eq!(Answer::from(3), Day05::solve(reader, Part::One).unwrap());
     }
 
     #[test]
     fn test_part_two_value_from_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
 
-        assert_eq!(14, PartTwoValue::try_from(reader).unwrap().get());
+        assert_eq!(Answer::from(14), Day05::solve(reader, Part::Two).unwrap());
+    }
+
+    #[test]
+    fn test_fresh_id_count_full_range() {
+        let fresh_id_ranges = FreshIdRanges::new(vec![1..=i64::MAX, 5..=i64::MAX]);
+
+        assert_eq!(i64::MAX, fresh_id_ranges.fresh_id_count().unwrap());
     }
 }
@@ -1,46 +1,26 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::ops::Range;
-use utils::FileReader;
+use utils::{checked, Answer, FileReader, Solution};
 
-static DAY_06_FILE: &str = "./resources/aoc_25/day_06.txt";
 static OPERATIONS_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"[\\*\\+] *").expect("Expected a valid regex for operations"));
 
-pub struct Day06Processor(String);
+pub struct Day06;
 
-impl Day06Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_06_FILE))
-    }
-
-    pub fn process(&self) {
-        self.process_part1();
-        self.process_part2();
-    }
+impl Solution for Day06 {
+    type Input = Day06Input;
 
-    fn process_part1(&self) {
-        let reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(reader) {
-            Ok(p1_value) => println!("AoC 25 Day 06 Part 1: {}", p1_value.get()),
-            Err(msg) => println!("AoC 25 Day 06 Part 1: Failed with message: {}", msg),
-        }
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        Day06Input::try_from(reader)
     }
 
-    fn process_part2(&self) {
-        let reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(reader) {
-            Ok(p2_value) => println!("AoC 25 Day 06 Part 2: {}", p2_value.get()),
-            Err(msg) => println!("AoC 25 Day 06 Part 2: Failed with message: {}", msg),
-        }
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::try_from(input)?.get().into())
     }
-}
 
-impl Default for Day06Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::try_from(input)?.get().into())
     }
 }
 
@@ -61,7 +41,7 @@ impl From<&str> for Operation {
 }
 
 #[derive(Debug, PartialEq)]
-struct Day06Input {
+pub struct Day06Input {
     worksheet_lines: Vec<String>,
     operations: String,
 }
@@ -70,13 +50,13 @@ impl TryFrom<FileReader> for Day06Input {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let mut worksheet_lines: Vec<String> = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .collect::<Vec<String>>();
+        let mut worksheet_lines: Vec<String> =
+            reader.read_lines()?.collect::<Result<Vec<String>, _>>()?;
 
         let Some(operations) = worksheet_lines.pop() else {
-            return Err(Self::Error::InvalidInput);
+            return Err(Self::Error::invalid_input(
+                "expected worksheet lines followed by a line of operations",
+            ));
         };
 
         Ok(Self {
@@ -106,16 +86,22 @@ impl PartOneAccumulator {
         Self { column_totals }
     }
 
-    fn add_value(&mut self, value: i64, column: usize) {
-        self.column_totals[column] += value;
+    fn add_value(&mut self, value: i64, column: usize) -> Result<(), crate::Error> {
+        let total = &mut self.column_totals[column];
+        *total = checked::add(*total, value, "column total")?;
+
+        Ok(())
     }
 
-    fn multiply_value(&mut self, value: i64, column: usize) {
-        self.column_totals[column] *= value;
+    fn multiply_value(&mut self, value: i64, column: usize) -> Result<(), crate::Error> {
+        let total = &mut self.column_totals[column];
+        *total = checked::mul(*total, value, "column total")?;
+
+        Ok(())
     }
 
-    fn total(&self) -> i64 {
-        self.column_totals.iter().sum()
+    fn total(&self) -> Result<i64, crate::Error> {
+        checked::sum(self.column_totals.iter().copied(), "worksheet total")
     }
 }
 
@@ -128,10 +114,10 @@ impl PartOneValue {
     }
 }
 
-impl TryFrom<Day06Input> for PartOneValue {
+impl TryFrom<&Day06Input> for PartOneValue {
     type Error = crate::Error;
 
-    fn try_from(value: Day06Input) -> Result<Self, Self::Error> {
+    fn try_from(value: &Day06Input) -> Result<Self, Self::Error> {
         let operations: Vec<Operation> = value
             .operations
             .split_whitespace()
@@ -148,27 +134,30 @@ impl TryFrom<Day06Input> for PartOneValue {
                 .collect::<Vec<&str>>()
                 .iter()
                 .map(|x| x.parse::<i64>())
-                .collect::<Result<Vec<i64>, std::num::ParseIntError>>()?;
+                .collect::<Result<Vec<i64>, std::num::ParseIntError>>()
+                .map_err(|err| {
+                    Self::Error::from(err)
+                        .with_source_line(&value.worksheet_lines[idx])
+                        .at_line(idx + 1)
+                })?;
 
             for (column, operation) in operations.iter().enumerate() {
-                let value = vals[column];
+                let Some(&value) = vals.get(column) else {
+                    return Err(Self::Error::invalid_input(format!(
+                        "expected {} numbers, one for each operation",
+                        operations.len()
+                    ))
+                    .with_source_line(&value.worksheet_lines[idx])
+                    .at_line(idx + 1));
+                };
                 match operation {
-                    Operation::Add => accumulator.add_value(value, column),
-                    Operation::Multiply => accumulator.multiply_value(value, column),
+                    Operation::Add => accumulator.add_value(value, column)?,
+                    Operation::Multiply => accumulator.multiply_value(value, column)?,
                 }
             }
         }
 
-        Ok(Self(accumulator.total()))
-    }
-}
-
-impl TryFrom<FileReader> for PartOneValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = Day06Input::try_from(reader)?;
-        Self::try_from(input)
+        Ok(Self(accumulator.total()?))
     }
 }
 
@@ -187,11 +176,16 @@ impl PartTwoValue {
     }
 }
 
-impl TryFrom<Day06Input> for PartTwoValue {
+impl TryFrom<&Day06Input> for PartTwoValue {
     type Error = crate::Error;
 
-    fn try_from(value: Day06Input) -> Result<Self, Self::Error> {
-        let worksheet_size = value.worksheet_lines[0].len();
+    fn try_from(value: &Day06Input) -> Result<Self, Self::Error> {
+        let worksheet_size = value
+            .worksheet_lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
 
         // Create the ranged operations
         let matches = OPERATIONS_RE
@@ -219,40 +213,39 @@ impl TryFrom<Day06Input> for PartTwoValue {
 
             for idx in operation.range {
                 let mut digits: Vec<char> = vec![];
-                for line in value.worksheet_lines.clone() {
+                for line in value.worksheet_lines.iter() {
                     if let Some(this_digit) = line.chars().nth(idx) {
                         if this_digit != ' ' {
                             digits.push(this_digit)
                         }
                     }
                 }
-                nums.push(digits.into_iter().collect::<String>().parse::<i64>()?);
+                let number = digits.into_iter().collect::<String>();
+                nums.push(
+                    number
+                        .parse::<i64>()
+                        .map_err(|err| Self::Error::from(err).with_text(&number))?,
+                );
             }
 
-            if let Some(this_column) = match operation.operation {
-                Operation::Add => Some(nums.into_iter().sum()),
-                Operation::Multiply => nums.into_iter().reduce(|a, b| a * b),
-            } {
-                columns.push(this_column)
+            if nums.is_empty() {
+                continue;
             }
-        }
 
-        Ok(PartTwoValue(columns.into_iter().sum()))
-    }
-}
-
-impl TryFrom<FileReader> for PartTwoValue {
-    type Error = crate::Error;
+            columns.push(match operation.operation {
+                Operation::Add => checked::sum(nums, "column total")?,
+                Operation::Multiply => checked::product(nums, "column total")?,
+            });
+        }
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = Day06Input::try_from(reader)?;
-        Self::try_from(input)
+        Ok(PartTwoValue(checked::sum(columns, "worksheet total")?))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::Part;
 
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_06_sample.txt";
 
@@ -287,7 +280,7 @@ mod tests {
 
         assert_eq!(
             PartOneValue(4277556),
-            PartOneValue::try_from(input).unwrap()
+            PartOneValue::try_from(&input).unwrap()
         );
     }
 
@@ -296,8 +289,8 @@ mod tests {
         let reader = FileReader::new(SAMPLE_FILE);
 
         assert_eq!(
-            PartOneValue(4277556),
-            PartOneValue::try_from(reader).unwrap()
+            Answer::from(4277556),
+            Day06::solve(reader, Part::One).unwrap()
         );
     }
 
@@ -308,7 +301,7 @@ mod tests {
 
         assert_eq!(
             PartTwoValue(3263827),
-            PartTwoValue::try_from(input).unwrap()
+            PartTwoValue::try_from(&input).unwrap()
         );
     }
 
@@ -317,8 +310,42 @@ mod tests {
         let reader = FileReader::new(SAMPLE_FILE);
 
         assert_eq!(
-            PartTwoValue(3263827),
-            PartTwoValue::try_from(reader).unwrap()
+            Answer::from(3263827),
+            Day06::solve(reader, Part::Two).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_column_total_overflow() {
+        let reader = FileReader::from_text("9999999999\n9999999999\n9999999999\n*\n");
+        let input = Day06Input::try_from(reader).unwrap();
+
+        assert_eq!(
+            "column total overflowed",
+            PartOneValue::try_from(&input).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "column total overflowed",
+            PartTwoValue::try_from(&input).unwrap_err().to_string()
         );
     }
+
+    #[test]
+    fn test_missing_column() {
+        let reader = FileReader::from_text("1 2 3\n4 5\n+ * +\n");
+        let input = Day06Input::try_from(reader).unwrap();
+
+        assert_eq!(
+            "line 2: expected 3 numbers, one for each operation",
+            PartOneValue::try_from(&input).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_operations_only() {
+        let input = Day06Input::try_from(FileReader::from_text("+ *\n")).unwrap();
+
+        assert_eq!(PartOneValue(1), PartOneValue::try_from(&input).unwrap());
+        assert!(PartTwoValue::try_from(&input).is_err());
+    }
 }
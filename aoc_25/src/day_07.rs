@@ -1,65 +1,50 @@
-use std::{cmp::max, collections::HashSet};
-use utils::FileReader;
+use num_bigint::BigInt;
+use std::collections::HashSet;
+use utils::{
+    checked::{self, Integer},
+    Answer, FileReader, Grid, Solution,
+};
 
-static DAY_07_FILE: &str = "./resources/aoc_25/day_07.txt";
+pub struct Day07;
 
-pub struct Day07Processor(String);
+impl Solution for Day07 {
+    type Input = Day07Input;
 
-impl Day07Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_07_FILE))
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        Day07Input::try_from(reader)
     }
 
-    pub fn process(&self) {
-        self.process_part1();
-        self.process_part2();
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartOneValue::from(input).get().into())
     }
 
-    fn process_part1(&self) {
-        let reader = FileReader::new(&self.0);
-
-        match PartOneValue::try_from(reader) {
-            Ok(p1_value) => println!("AoC 25 Day 07 Part 1: {}", p1_value.get()),
-            Err(msg) => println!("AoC 25 Day 07 Part 1: Failed with message: {}", msg),
-        }
-    }
-
-    fn process_part2(&self) {
-        let reader = FileReader::new(&self.0);
-
-        match PartTwoValue::try_from(reader) {
-            Ok(p2_value) => println!("AoC 25 Day 07 Part 2: {}", p2_value.get()),
-            Err(msg) => println!("AoC 25 Day 07 Part 2: Failed with message: {}", msg),
-        }
-    }
-}
-
-impl Default for Day07Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(PartTwoValue::try_from(input)?.get().clone().into())
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct TachyonManifold {
-    diagram: Vec<Vec<char>>,
+struct TachyonManifold<'a> {
+    diagram: &'a Grid<char>,
     tracker: BeamTracker,
 }
 
-impl TachyonManifold {
-    fn create(input: Day07Input) -> Self {
-        let manifold_size = &input.lines.len();
+impl<'a> TachyonManifold<'a> {
+    fn create(input: &'a Day07Input) -> Self {
+        let right_index = input.diagram.width().saturating_sub(1);
         Self {
-            diagram: input.lines,
-            tracker: BeamTracker::new(*manifold_size),
+            diagram: &input.diagram,
+            tracker: BeamTracker::new(right_index),
         }
     }
 
     // Determines the total number of splits from a bream traversal of the diagram.
     fn traversal_splits(&mut self) -> i64 {
-        self.tracker.init(self.diagram[0].clone());
+        if let Some(first_row) = self.diagram.row(0) {
+            self.tracker.init(first_row);
+        }
 
-        for idx in 1..self.diagram.len() {
+        for idx in 1..self.diagram.height() {
             self.traverse(idx);
         }
 
@@ -67,7 +52,11 @@ impl TachyonManifold {
     }
 
     fn traverse(&mut self, row_idx: usize) {
-        for (pos, value) in self.diagram[row_idx].clone().iter().enumerate() {
+        let Some(row) = self.diagram.row(row_idx) else {
+            return;
+        };
+
+        for (pos, value) in row.iter().enumerate() {
             if *value == '^' {
                 self.tracker.split(pos);
             }
@@ -92,7 +81,7 @@ impl BeamTracker {
     }
 
     // Looks for the entry point of the beam and tracks that.
-    fn init(&mut self, row: Vec<char>) {
+    fn init(&mut self, row: &[char]) {
         for (pos, value) in row.iter().enumerate() {
             if *value == 'S' {
                 self.traversal_indexes.insert(pos);
@@ -123,67 +112,73 @@ impl BeamTracker {
 }
 
 #[derive(Debug, PartialEq)]
-struct QuantumTachyonManifold {
-    diagram: Vec<Vec<char>>,
+struct QuantumTachyonManifold<'a> {
+    diagram: &'a Grid<char>,
 }
 
-impl QuantumTachyonManifold {
-    fn create(input: Day07Input) -> Self {
+impl<'a> QuantumTachyonManifold<'a> {
+    fn create(input: &'a Day07Input) -> Self {
         Self {
-            diagram: input.lines,
+            diagram: &input.diagram,
         }
     }
 
-    fn build_grid(rows: usize, columns: usize) -> Vec<Vec<i64>> {
-        let cols = vec![0; columns];
-        vec![cols; rows]
-    }
+    // Returns the total number of paths across all timelines, or an error if
+    // the count doesn't fit in `T`.
+    fn traverse_all<T: Integer>(&self) -> Result<T, crate::Error> {
+        let mut grid: Grid<T> = Grid::new(self.diagram.width(), self.diagram.height(), T::zero());
 
-    // Returns the total number of paths across all timelines.
-    fn traverse_all(&mut self) -> i64 {
-        let mut grid =
-            QuantumTachyonManifold::build_grid(self.diagram.len(), self.diagram[0].len());
+        for ((row, col), value) in self.diagram.iter() {
+            if *value == 'S' {
+                grid[(row, col)] = T::one();
+                continue;
+            }
 
-        for (row, line) in self.diagram.iter().enumerate() {
-            for (col, value) in line.iter().enumerate() {
-                if *value == 'S' {
-                    grid[row][col] = 1;
-                    continue;
-                }
+            if *value == '.' && row > 0 {
+                grid[(row, col)] = checked::add(
+                    grid[(row, col)].clone(),
+                    grid[(row - 1, col)].clone(),
+                    "path count",
+                )?;
+                continue;
+            }
 
-                if *value == '.' && row > 0 {
-                    grid[row][col] += grid[row - 1][col];
-                    continue;
-                }
+            if *value == '^' && row > 0 {
+                let above = grid[(row - 1, col)].clone();
 
-                if *value == '^' {
-                    for idx in [col - 1, col + 1] {
-                        grid[row][idx] = max(grid[row - 1][col] + grid[row][idx], grid[row][idx]);
+                // A split off either edge of the diagram leaves the manifold.
+                for idx in col.checked_sub(1).into_iter().chain([col + 1]) {
+                    if let Some(paths) = grid.get_mut((row, idx)) {
+                        *paths = checked::add(above.clone(), paths.clone(), "path count")?;
                     }
                 }
             }
         }
 
-        grid[grid.len() - 1].iter().sum()
+        let last_row = grid.height().checked_sub(1).and_then(|row| grid.row(row));
+
+        checked::sum(last_row.into_iter().flatten().cloned(), "path count")
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct Day07Input {
-    lines: Vec<Vec<char>>,
+pub struct Day07Input {
+    diagram: Grid<char>,
 }
 
 impl TryFrom<FileReader> for Day07Input {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let lines: Vec<Vec<char>> = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .map(|x| x.chars().collect())
-            .collect();
+        let diagram = reader.read_grid()?;
 
-        Ok(Self { lines })
+        if diagram.is_empty() {
+            return Err(Self::Error::invalid_input(
+                "expected at least one row of the manifold",
+            ));
+        }
+
+        Ok(Self { diagram })
     }
 }
 
@@ -196,35 +191,32 @@ impl PartOneValue {
     }
 }
 
-impl TryFrom<FileReader> for PartOneValue {
-    type Error = crate::Error;
-
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = Day07Input::try_from(reader)?;
+impl From<&Day07Input> for PartOneValue {
+    fn from(input: &Day07Input) -> Self {
         let mut manifold = TachyonManifold::create(input);
 
-        Ok(Self(manifold.traversal_splits()))
+        Self(manifold.traversal_splits())
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PartTwoValue(i64);
+// The path count doubles with every split, so it is kept as a `BigInt` rather
+// than any fixed-width integer.
+struct PartTwoValue(BigInt);
 
 impl PartTwoValue {
-    #[allow(dead_code)]
-    fn get(&self) -> i64 {
-        self.0
+    fn get(&self) -> &BigInt {
+        &self.0
     }
 }
 
-impl TryFrom<FileReader> for PartTwoValue {
+impl TryFrom<&Day07Input> for PartTwoValue {
     type Error = crate::Error;
 
-    fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input = Day07Input::try_from(reader)?;
-        let mut manifold = QuantumTachyonManifold::create(input);
+    fn try_from(input: &Day07Input) -> Result<Self, Self::Error> {
+        let manifold = QuantumTachyonManifold::create(input);
 
-        Ok(Self(manifold.traverse_all()))
+        Ok(Self(manifold.traverse_all()?))
     }
 }
 
@@ -241,23 +233,92 @@ mod tests {
 
         assert_eq!(
             ".......S.......".chars().collect::<Vec<char>>(),
-            input.lines[0]
+            input.diagram.row(0).unwrap()
         )
     }
 
     #[test]
     fn test_part_one_value_from_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
-        let p1_value = PartOneValue::try_from(reader).unwrap();
+        let input = Day07Input::try_from(reader).unwrap();
 
-        assert_eq!(21, p1_value.get())
+        assert_eq!(21, PartOneValue::from(&input).get())
     }
 
     #[test]
     fn test_part_two_value_from_reader() {
         let reader = FileReader::new(SAMPLE_FILE);
-        let p2_value = PartTwoValue::try_from(reader).unwrap();
+        let input = Day07Input::try_from(reader).unwrap();
+
+        assert_eq!(
+            &BigInt::from(40),
+            PartTwoValue::try_from(&input).unwrap().get()
+        )
+    }
+
+    // A triangle of splitters where every beam splits at each level, giving
+    // 2^levels paths.
+    fn doubling_input(levels: usize) -> Day07Input {
+        let width = 2 * levels + 3;
+        let center = levels + 1;
+        let mut lines = vec![];
+
+        let mut start = vec!['.'; width];
+        start[center] = 'S';
+        lines.push(start);
+
+        for level in 0..levels {
+            let mut splitters = vec!['.'; width];
+            for offset in 0..=level {
+                splitters[center - level + 2 * offset] = '^';
+            }
+            lines.push(splitters);
+            lines.push(vec!['.'; width]);
+        }
+
+        let diagram = Grid::from_rows(lines).unwrap();
+
+        Day07Input { diagram }
+    }
 
-        assert_eq!(40, p2_value.get())
+    #[test]
+    fn test_path_count_width() {
+        let input = doubling_input(10);
+        let manifold = QuantumTachyonManifold::create(&input);
+
+        assert_eq!(1024, manifold.traverse_all::<i16>().unwrap());
+        assert_eq!(1024, manifold.traverse_all::<i128>().unwrap());
+        assert_eq!(
+            "path count overflowed",
+            manifold.traverse_all::<i8>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_splitters_at_edges() {
+        for (diagram, paths) in [("S..\n^..\n", 1), ("..S\n..^\n", 1), ("^S^\n.^.\n", 2)] {
+            let input = Day07Input::try_from(FileReader::from_text(diagram)).unwrap();
+
+            assert_eq!(1, PartOneValue::from(&input).get());
+            assert_eq!(
+                &BigInt::from(paths),
+                PartTwoValue::try_from(&input).unwrap().get()
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_diagram() {
+        let empty = Day07Input::try_from(FileReader::from_text(""));
+        let ragged = Day07Input::try_from(FileReader::from_text("..S..\n..^\n"));
+
+        assert_eq!(
+            "expected at least one row of the manifold",
+            empty.unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2: expected every row to be 5 wide",
+            ragged.unwrap_err().to_string()
+        );
     }
 }
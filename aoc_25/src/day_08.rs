@@ -1,188 +1,97 @@
 use disjoint::DisjointSetVec;
 use itertools::Itertools;
-use std::{
-    cmp::{Ord, Ordering},
-    collections::{BTreeMap, HashMap},
-    hash::{Hash, Hasher},
-};
-use utils::FileReader;
+use std::collections::HashMap;
+use utils::{checked, Answer, FileReader, Point3, Solution};
 
-static DAY_08_FILE: &str = "./resources/aoc_25/day_08.txt";
+pub struct Day08;
 
-pub struct Day08Processor(String);
+impl Solution for Day08 {
+    type Input = PlaygroundDecoration;
 
-impl Day08Processor {
-    fn new() -> Self {
-        Self(String::from(DAY_08_FILE))
+    fn parse(reader: FileReader) -> Result<Self::Input, crate::Error> {
+        PlaygroundDecoration::try_from(reader)
     }
 
-    pub fn process(&self) {
-        self.process_part1();
-        self.process_part2();
+    fn part_one(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(input.calculate_circuits(1000)?.into())
     }
 
-    fn process_part1(&self) {
-        // println!("Running part 1 of day 8 using: {}", self.0);
-        let reader = FileReader::new(&self.0);
-
-        match PlaygroundDecoration::try_from(reader) {
-            Ok(pd) => println!("AoC 25 Day 08 Part 1: {}", pd.calculate_circuits(1000)),
-            Err(msg) => println!("AoC 25 Day 08 Part 1: Failed with message: {}", msg),
-        }
-    }
-
-    fn process_part2(&self) {
-        let reader = FileReader::new(&self.0);
-
-        match PlaygroundDecoration::try_from(reader) {
-            Ok(pd) => println!("AoC 25 Day 08 Part 2: {}", pd.join_all()),
-            Err(msg) => println!("AoC 25 Day 08 Part 2: Failed with message: {}", msg),
-        }
-    }
-}
-
-impl Default for Day08Processor {
-    fn default() -> Self {
-        Self::new()
+    fn part_two(input: &Self::Input) -> Result<Answer, crate::Error> {
+        Ok(input.join_all()?.into())
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct Location(i64, i64, i64);
+struct JunctionBox(Point3);
 
-impl Location {
-    fn x(&self) -> i64 {
+impl JunctionBox {
+    fn location(&self) -> Point3 {
         self.0
     }
 
-    fn y(&self) -> i64 {
-        self.1
-    }
-
-    fn z(&self) -> i64 {
-        self.2
-    }
-
-    fn distance_from(&self, other: Location) -> Distance {
-        let x = (self.x() - other.x()).pow(2);
-        let y = (self.y() - other.y()).pow(2);
-        let z = (self.z() - other.z()).pow(2);
-
-        let total = x + y + z;
-        Distance((total as f64).sqrt())
+    fn distance_from(&self, other: &JunctionBox) -> Result<i64, crate::Error> {
+        self.location()
+            .squared_euclidean_distance(&other.location())
     }
 }
 
-impl TryFrom<String> for Location {
+impl TryFrom<String> for JunctionBox {
     type Error = crate::Error;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let vals = value
-            .split(",")
-            .map(|x| x.parse::<i64>())
-            .collect::<Result<Vec<i64>, std::num::ParseIntError>>()?;
-
-        Ok(Self(vals[0], vals[1], vals[2]))
-    }
-}
-
-#[derive(Clone, Copy, Debug)]
-struct Distance(f64);
-
-impl Distance {
-    fn value(&self) -> f64 {
-        self.0
-    }
-
-    /// Normalize to a canonical bit representation so that:
-    /// - All NaNs hash/compare equal to one canonical NaN.
-    /// - -0.0 and 0.0 are treated the same.
-    /// - Otherwise, use the raw IEEE-754 bits.
-    fn norm_bits(self) -> u64 {
-        let x = self.0;
-
-        if x == 0.0 {
-            // Collapse -0.0 to +0.0
-            0.0f64.to_bits()
-        } else if x.is_nan() {
-            // Canonical quiet NaN (chosen representative)
-            // 0x7ff8_0000_0000_0000 is a common canonical qNaN pattern.
-            0x7ff8_0000_0000_0000u64
-        } else {
-            x.to_bits()
+        let mut column = 1;
+        let mut vals = vec![];
+
+        for x in value.split(",") {
+            vals.push(
+                x.parse::<i64>()
+                    .map_err(|err| Self::Error::from(err).with_text(x).at_column(column))?,
+            );
+            column += x.len() + 1;
         }
-    }
-}
-
-impl PartialEq for Distance {
-    fn eq(&self, other: &Self) -> bool {
-        self.norm_bits() == other.norm_bits()
-    }
-}
-
-impl Eq for Distance {}
-
-impl Hash for Distance {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.norm_bits().hash(state);
-    }
-}
-
-impl PartialOrd for Distance {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Distance {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.value().total_cmp(&other.value())
-    }
-}
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-struct JunctionBox(Location);
+        let [x, y, z] = vals[..] else {
+            return Err(
+                Self::Error::invalid_input("expected three comma separated numbers")
+                    .with_text(value),
+            );
+        };
 
-impl JunctionBox {
-    fn location(&self) -> Location {
-        self.0
-    }
-}
-
-impl TryFrom<String> for JunctionBox {
-    type Error = crate::Error;
-
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let location = Location::try_from(value)?;
-
-        Ok(Self(location))
+        Ok(Self(Point3::new(x, y, z)))
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct PlaygroundDecoration {
+pub struct PlaygroundDecoration {
     junction_boxes: Vec<JunctionBox>,
 }
 
 impl PlaygroundDecoration {
-    fn calculate_circuits(&self, num_pairs: usize) -> i64 {
-        let mut distances: BTreeMap<Distance, (usize, usize)> = BTreeMap::new();
-        let num_junction_boxes = self.junction_boxes.len();
-
-        for i in 0..(num_junction_boxes - 1) {
-            for j in (i + 1)..num_junction_boxes {
-                let distance: Distance = self.junction_boxes[i]
-                    .location()
-                    .distance_from(self.junction_boxes[j].location());
-                distances.insert(distance, (i, j));
+    // Every pair of junction boxes as `(squared distance, i, j)`, closest
+    // first. Pairs the same distance apart are all kept.
+    fn pairs_by_distance(&self) -> Result<Vec<(i64, usize, usize)>, crate::Error> {
+        let mut pairs = vec![];
+        let num_boxes = self.junction_boxes.len();
+
+        for i in 0..num_boxes {
+            for j in (i + 1)..num_boxes {
+                let distance = self.junction_boxes[i].distance_from(&self.junction_boxes[j])?;
+                pairs.push((distance, i, j));
             }
         }
 
+        pairs.sort_unstable();
+        Ok(pairs)
+    }
+
+    fn calculate_circuits(&self, num_pairs: usize) -> Result<i64, crate::Error> {
+        let distances = self.pairs_by_distance()?;
+
         let mut boxes: DisjointSetVec<JunctionBox> =
             DisjointSetVec::from(self.junction_boxes.clone());
 
         let mut num_connected = 0;
-        for (_, (i, j)) in distances.iter() {
+        for (_, i, j) in distances.iter() {
             boxes.join(*i, *j);
             num_connected += 1;
 
@@ -197,39 +106,35 @@ impl PlaygroundDecoration {
             *circuits.entry(root).or_insert(0) += 1;
         }
 
-        circuits
-            .iter()
-            .sorted_by(|a, b| b.1.cmp(a.1))
-            .take(3)
-            .map(|(_, count)| count)
-            .product()
+        let largest = circuits.into_values().sorted_by(|a, b| b.cmp(a)).take(3);
+
+        checked::product(largest, "circuit size product")
     }
 
-    fn join_all(&self) -> i64 {
-        let mut distances: BTreeMap<Distance, (usize, usize)> = BTreeMap::new();
-        let num_boxes = self.junction_boxes.len();
-
-        for i in 0..(num_boxes - 1) {
-            for j in (i + 1)..num_boxes {
-                let distance = self.junction_boxes[i]
-                    .location()
-                    .distance_from(self.junction_boxes[j].location());
-                distances.insert(distance, (i, j));
-            }
-        }
+    fn join_all(&self) -> Result<i64, crate::Error> {
+        let distances = self.pairs_by_distance()?;
 
         let mut boxes: DisjointSetVec<JunctionBox> =
             DisjointSetVec::from(self.junction_boxes.clone());
-        let mut last_join: [(usize, usize); 1] = [(0, 1)];
+        let mut last_join: Option<(usize, usize)> = None;
 
-        for (_, (i, j)) in distances.iter() {
+        for (_, i, j) in distances.iter() {
             if boxes.join(*i, *j) {
-                last_join[0] = (*i, *j);
+                last_join = Some((*i, *j));
             }
         }
 
-        self.junction_boxes[last_join[0].0].location().x()
-            * self.junction_boxes[last_join[0].1].location().x()
+        let Some((i, j)) = last_join else {
+            return Err(crate::Error::invalid_input(
+                "expected at least two junction boxes to join",
+            ));
+        };
+
+        checked::mul(
+            self.junction_boxes[i].location().x,
+            self.junction_boxes[j].location().x,
+            "last junction product",
+        )
     }
 }
 
@@ -237,15 +142,7 @@ impl TryFrom<FileReader> for PlaygroundDecoration {
     type Error = crate::Error;
 
     fn try_from(reader: FileReader) -> Result<Self, Self::Error> {
-        let input_lines = reader
-            .read_lines()?
-            .map_while(Result::ok)
-            .collect::<Vec<String>>();
-
-        let junction_boxes = input_lines
-            .into_iter()
-            .map(JunctionBox::try_from)
-            .collect::<Result<Vec<JunctionBox>, crate::Error>>()?;
+        let junction_boxes = reader.parse_lines(JunctionBox::try_from)?;
 
         Ok(Self { junction_boxes })
     }
@@ -258,17 +155,28 @@ mod tests {
     static SAMPLE_FILE: &str = "../test-resources/aoc_25/day_08_sample.txt";
 
     #[test]
-    fn test_location_from_string() {
+    fn test_invalid_location_diagnostic() {
+        let reader = FileReader::from_text("162,817,812\n57,6l8,906\n");
+        let expected = [
+            " --> <input>:2:4",
+            "  |",
+            "2 | 57,6l8,906",
+            "  |    ^^^ invalid digit found in string",
+        ];
+
         assert_eq!(
-            Location(162, 817, 812),
-            Location::try_from(String::from("162,817,812")).unwrap()
+            Some(expected.join("\n")),
+            PlaygroundDecoration::try_from(reader)
+                .unwrap_err()
+                .diagnostic()
         );
+        assert!(JunctionBox::try_from(String::from("1,2")).is_err());
     }
 
     #[test]
     fn test_junction_box_from_string() {
         assert_eq!(
-            JunctionBox(Location(162, 817, 812)),
+            JunctionBox(Point3::new(162, 817, 812)),
             JunctionBox::try_from(String::from("162,817,812")).unwrap()
         )
     }
@@ -280,7 +188,7 @@ mod tests {
 
         assert_eq!(20, playground_decoration.junction_boxes.len());
         assert_eq!(
-            JunctionBox(Location(162, 817, 812)),
+            JunctionBox(Point3::new(162, 817, 812)),
             playground_decoration.junction_boxes[0]
         );
     }
@@ -290,7 +198,7 @@ mod tests {
         let reader = FileReader::new(SAMPLE_FILE);
         let playground_decoration = PlaygroundDecoration::try_from(reader).unwrap();
 
-        assert_eq!(40, playground_decoration.calculate_circuits(10));
+        assert_eq!(40, playground_decoration.calculate_circuits(10).unwrap());
     }
 
     #[test]
@@ -298,6 +206,27 @@ mod tests {
         let reader = FileReader::new(SAMPLE_FILE);
         let playground_decoration = PlaygroundDecoration::try_from(reader).unwrap();
 
-        assert_eq!(25272, playground_decoration.join_all());
+        assert_eq!(25272, playground_decoration.join_all().unwrap());
+    }
+
+    #[test]
+    fn test_too_few_junction_boxes() {
+        for text in ["", "1,2,3\n"] {
+            let playground = PlaygroundDecoration::try_from(FileReader::from_text(text)).unwrap();
+
+            assert_eq!(1, playground.calculate_circuits(10).unwrap());
+            assert_eq!(
+                "expected at least two junction boxes to join",
+                playground.join_all().unwrap_err().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_pairs_at_equal_distances() {
+        let text = "0,0,0\n1,0,0\n2,0,0\n";
+        let playground = PlaygroundDecoration::try_from(FileReader::from_text(text)).unwrap();
+
+        assert_eq!(3, playground.calculate_circuits(2).unwrap());
     }
 }
@@ -1,35 +1,12 @@
-mod day_01;
-mod day_02;
-mod day_03;
-mod day_04;
-mod day_05;
-mod day_06;
-mod day_07;
-mod day_08;
-mod error;
-
-pub use error::Error;
-
-use day_01::Day01Processor;
-use day_02::Day02Processor;
-use day_03::Day03Processor;
-use day_04::Day04Processor;
-use day_05::Day05Processor;
-use day_06::Day06Processor;
-use day_07::Day07Processor;
-use day_08::Day08Processor;
-
-pub struct AoC25Processor {}
-
-impl AoC25Processor {
-    pub fn process() {
-        Day01Processor::default().process();
-        Day02Processor::default().process();
-        Day03Processor::default().process();
-        Day04Processor::default().process();
-        Day05Processor::default().process();
-        Day06Processor::default().process();
-        Day07Processor::default().process();
-        Day08Processor::default().process();
-    }
-}
+pub use utils::Error;
+
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
+pub mod day_06;
+pub mod day_07;
+pub mod day_08;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -0,0 +1,211 @@
+use crate::{
+    limit::{self, Run},
+    report::{label, millis, Status},
+};
+use serde::Serialize;
+use std::{fmt, time::Duration};
+use utils::{Entry, FileReader, Puzzle};
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let min = *sorted.first()?;
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+
+        // Sample standard deviation, which is zero for a single sample.
+        let variance = if secs.len() > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (secs.len() - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            min,
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The timings of a single part run repeatedly.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BenchRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub error: Option<String>,
+    pub iterations: usize,
+    pub parse_median_ms: Option<f64>,
+    pub min_ms: Option<f64>,
+    pub median_ms: Option<f64>,
+    pub mean_ms: Option<f64>,
+    pub stddev_ms: Option<f64>,
+}
+
+impl BenchRecord {
+    fn new(entry: &Entry, status: Status, error: Option<String>) -> Self {
+        Self {
+            year: entry.year,
+            day: entry.day,
+            part: entry.part.number(),
+            status,
+            error,
+            iterations: 0,
+            parse_median_ms: None,
+            min_ms: None,
+            median_ms: None,
+            mean_ms: None,
+            stddev_ms: None,
+        }
+    }
+
+    fn measured(entry: &Entry, parse_times: &[Duration], solve_times: &[Duration]) -> Self {
+        let mut record = Self::new(entry, Status::Ok, None);
+        record.iterations = solve_times.len();
+        record.parse_median_ms = Stats::from_samples(parse_times).map(|s| millis(s.median));
+
+        if let Some(stats) = Stats::from_samples(solve_times) {
+            record.min_ms = Some(millis(stats.min));
+            record.median_ms = Some(millis(stats.median));
+            record.mean_ms = Some(millis(stats.mean));
+            record.stddev_ms = Some(millis(stats.stddev));
+        }
+
+        record
+    }
+}
+
+impl fmt::Display for BenchRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", label(self.year, self.day, self.part))?;
+
+        match self.status {
+            Status::Ok => write!(
+                f,
+                "min {:.3} ms, median {:.3} ms, mean {:.3} ms, stddev {:.3} ms over {} runs (parse median {:.3} ms)",
+                self.min_ms.unwrap_or_default(),
+                self.median_ms.unwrap_or_default(),
+                self.mean_ms.unwrap_or_default(),
+                self.stddev_ms.unwrap_or_default(),
+                self.iterations,
+                self.parse_median_ms.unwrap_or_default()
+            ),
+            Status::Failed => write!(
+                f,
+                "Failed with message: {}",
+                self.error.as_deref().unwrap_or_default()
+            ),
+            Status::TimedOut => write!(f, "Timed out"),
+        }
+    }
+}
+
+/// Runs a part `warmup` times without recording anything, then `iterations`
+/// more times recording how long parsing and solving took. A failure or a run
+/// over the time limit stops the benchmark for that part.
+pub fn bench(
+    puzzle: &Puzzle,
+    entry: &Entry,
+    reader: impl Fn() -> FileReader,
+    limit: Option<Duration>,
+    warmup: usize,
+    iterations: usize,
+) -> BenchRecord {
+    let mut parse_times = vec![];
+    let mut solve_times = vec![];
+
+    for run in 0..warmup + iterations {
+        let outcome = match limit::solve_day(puzzle, reader(), &[entry.part], limit).pop() {
+            Some(Run::Finished(outcome)) => outcome,
+            _ => return BenchRecord::new(entry, Status::TimedOut, None),
+        };
+
+        if let Err(msg) = outcome.answer {
+            return BenchRecord::new(entry, Status::Failed, Some(msg));
+        }
+
+        if run >= warmup {
+            parse_times.push(outcome.parse_time);
+            solve_times.push(outcome.solve_time);
+        }
+    }
+
+    BenchRecord::measured(entry, &parse_times, &solve_times)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Part;
+
+    fn millis_samples(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect()
+    }
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let stats = Stats::from_samples(&millis_samples(&[5, 1, 3])).unwrap();
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(3), stats.mean);
+        assert_eq!(Duration::from_millis(2), stats.stddev);
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let stats = Stats::from_samples(&millis_samples(&[4, 2, 8, 6])).unwrap();
+
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_millis(5), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty() {
+        let stats = Stats::from_samples(&millis_samples(&[7])).unwrap();
+
+        assert_eq!(Duration::ZERO, stats.stddev);
+        assert_eq!(None, Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn test_bench_record_display() {
+        let entry = Entry {
+            year: 25,
+            day: 3,
+            part: Part::One,
+        };
+        let record =
+            BenchRecord::measured(&entry, &millis_samples(&[1, 1]), &millis_samples(&[2, 4]));
+
+        assert_eq!(
+            "AoC 25 Day 03 Part 1: min 2.000 ms, median 3.000 ms, mean 3.000 ms, stddev 1.414 ms over 2 runs (parse median 1.000 ms)",
+            record.to_string()
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::report::Format;
+use clap::{ArgGroup, Parser};
+use std::{
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use utils::{Entry, Part};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Runs Advent of Code solutions")]
+#[command(group(
+    ArgGroup::new("selection")
+        .required(true)
+        .multiple(true)
+        .args(["all", "list", "year", "day"])
+))]
+pub struct Cli {
+    /// Run every part of every day for every year
+    #[arg(long, conflicts_with_all = ["year", "day", "part"])]
+    pub all: bool,
+
+    /// List the available solutions instead of running them
+    #[arg(long)]
+    pub list: bool,
+
+    /// Year to run, e.g. 25 or 2025
+    #[arg(long, value_parser = parse_year)]
+    pub year: Option<u16>,
+
+    /// Day to run, or an inclusive range of days, e.g. 7 or 3..6
+    #[arg(long)]
+    pub day: Option<DayRange>,
+
+    /// Part to run (1 or 2). Both parts are run if not provided
+    #[arg(long)]
+    pub part: Option<Part>,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Number of days to run at the same time, with both parts of a day run
+    /// one after the other. Results are still printed in year, day and part
+    /// order
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: u32,
+
+    /// Seconds each part may run before it is reported as timed out, or 0 for no
+    /// limit. A timed out part keeps running in the background, outside of
+    /// --jobs [default: 10, or the config file's timeout]
+    #[arg(long)]
+    pub timeout: Option<f64>,
+
+    /// Run each part repeatedly and report timing statistics instead of answers
+    #[arg(long, conflicts_with = "list")]
+    pub bench: bool,
+
+    /// Number of timed runs per part in bench mode
+    #[arg(long, default_value_t = 10, requires = "bench", value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+
+    /// Number of untimed runs per part before timing starts in bench mode
+    #[arg(long, default_value_t = 3, requires = "bench")]
+    pub warmup: u32,
+
+    /// Compare every answer with the recorded answers and fail on any mismatch
+    #[arg(long, conflicts_with_all = ["list", "bench"])]
+    pub verify: bool,
+
+    /// File holding the recorded answers used by --verify [default: answers.toml
+    /// in the input directory]
+    #[arg(long)]
+    pub answers: Option<PathBuf>,
+
+    /// Directory holding the inputs, laid out as aoc_[yy]/day_[dd].txt
+    /// [default: the resources directory at the workspace root]
+    #[arg(long, env = "AOC_INPUT_DIR")]
+    pub input_dir: Option<PathBuf>,
+
+    /// Input file to run instead of the one in the input directory, or - to read
+    /// from stdin. Only valid when a single day is selected
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// Same as --input, e.g. `cat input.txt | aoc_runner --year 25 --day 5 -`
+    #[arg(value_name = "INPUT", conflicts_with = "input")]
+    pub input_arg: Option<PathBuf>,
+
+    /// Config file setting input_dir and answers [default: aoc_runner.toml in
+    /// the current directory, if there is one]
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl Cli {
+    pub fn input(&self) -> Option<&Path> {
+        self.input.as_deref().or(self.input_arg.as_deref())
+    }
+
+    pub fn selection(&self) -> Selection {
+        Selection {
+            year: self.year,
+            days: self.day.as_ref().map(|d| d.0.clone()),
+            part: self.part,
+        }
+    }
+}
+
+// Accepts either the short (25) or the full (2025) form of a year and
+// normalizes it to the short form used by the solution crates.
+fn parse_year(value: &str) -> Result<u16, String> {
+    let year = value
+        .parse::<u16>()
+        .map_err(|_| format!("expected a year like 25 or 2025, got \"{}\"", value))?;
+
+    match year {
+        0..=99 => Ok(year),
+        2000..=2099 => Ok(year - 2000),
+        _ => Err(format!(
+            "expected a year like 25 or 2025, got \"{}\"",
+            value
+        )),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayRange(RangeInclusive<u8>);
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| {
+            day.trim()
+                .parse::<u8>()
+                .map_err(|_| format!("expected a day like 7 or a range like 3..6, got \"{}\"", s))
+        };
+
+        let (start, end) = match s.split_once("..") {
+            Some((start, end)) => {
+                let end = end.strip_prefix('=').unwrap_or(end);
+                (parse_day(start)?, parse_day(end)?)
+            }
+            None => {
+                let day = parse_day(s)?;
+                (day, day)
+            }
+        };
+
+        if start > end {
+            return Err(format!("day range \"{}\" is empty", s));
+        }
+
+        Ok(Self(start..=end))
+    }
+}
+
+/// The years, days and parts requested on the command line. Anything left
+/// unset matches everything.
+#[derive(Debug, Default, PartialEq)]
+pub struct Selection {
+    year: Option<u16>,
+    days: Option<RangeInclusive<u8>>,
+    part: Option<Part>,
+}
+
+impl Selection {
+    pub fn includes(&self, entry: &Entry) -> bool {
+        self.year.is_none_or(|y| y == entry.year)
+            && self
+                .days
+                .as_ref()
+                .is_none_or(|days| days.contains(&entry.day))
+            && self.part.is_none_or(|p| p == entry.part)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::entry;
+
+    #[test]
+    fn test_parse_year() {
+        assert_eq!(Ok(25), parse_year("25"));
+        assert_eq!(Ok(24), parse_year("2024"));
+        assert!(parse_year("1999").is_err());
+        assert!(parse_year("next").is_err());
+    }
+
+    #[test]
+    fn test_day_range_from_str() {
+        assert_eq!(DayRange(7..=7), "7".parse::<DayRange>().unwrap());
+        assert_eq!(DayRange(3..=6), "3..6".parse::<DayRange>().unwrap());
+        assert_eq!(DayRange(3..=6), "3..=6".parse::<DayRange>().unwrap());
+        assert!("6..3".parse::<DayRange>().is_err());
+        assert!("three".parse::<DayRange>().is_err());
+    }
+
+    #[test]
+    fn test_selection_from_args() {
+        let cli = Cli::try_parse_from(["aoc_runner", "--year", "25", "--day", "7", "--part", "2"])
+            .unwrap();
+        let selection = cli.selection();
+
+        assert!(selection.includes(&entry(25, 7, Part::Two)));
+        assert!(!selection.includes(&entry(25, 7, Part::One)));
+        assert!(!selection.includes(&entry(24, 7, Part::Two)));
+        assert!(!selection.includes(&entry(25, 6, Part::Two)));
+    }
+
+    #[test]
+    fn test_selection_all() {
+        let cli = Cli::try_parse_from(["aoc_runner", "--all"]).unwrap();
+
+        assert_eq!(Selection::default(), cli.selection());
+        assert!(Cli::try_parse_from(["aoc_runner", "--all", "--day", "3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc_runner"]).is_err());
+        assert!(Cli::try_parse_from(["aoc_runner", "--list"]).is_ok());
+    }
+
+    #[test]
+    fn test_bench_args() {
+        let cli =
+            Cli::try_parse_from(["aoc_runner", "--all", "--bench", "--iterations", "5"]).unwrap();
+
+        assert!(cli.bench);
+        assert_eq!(5, cli.iterations);
+        assert_eq!(3, cli.warmup);
+        assert!(Cli::try_parse_from(["aoc_runner", "--all", "--iterations", "5"]).is_err());
+        assert!(
+            Cli::try_parse_from(["aoc_runner", "--all", "--bench", "--iterations", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_args() {
+        let cli = Cli::try_parse_from(["aoc_runner", "--all", "--verify"]).unwrap();
+
+        assert!(cli.verify);
+        assert_eq!(None, cli.answers);
+        assert!(Cli::try_parse_from(["aoc_runner", "--all", "--verify", "--bench"]).is_err());
+    }
+
+    #[test]
+    fn test_input_args() {
+        let cli = Cli::try_parse_from([
+            "aoc_runner",
+            "--year",
+            "25",
+            "--day",
+            "3",
+            "--input",
+            "sample.txt",
+            "--input-dir",
+            "inputs",
+        ])
+        .unwrap();
+
+        assert_eq!(Some(Path::new("sample.txt")), cli.input());
+        assert_eq!(Some(PathBuf::from("inputs")), cli.input_dir);
+
+        let stdin = Cli::try_parse_from(["aoc_runner", "--day", "5", "-"]).unwrap();
+        assert_eq!(Some(Path::new("-")), stdin.input());
+        assert!(Cli::try_parse_from(["aoc_runner", "--day", "5", "--input", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_jobs() {
+        let default = Cli::try_parse_from(["aoc_runner", "--all"]).unwrap();
+        let jobs = Cli::try_parse_from(["aoc_runner", "--all", "-j", "4"]).unwrap();
+
+        assert_eq!(1, default.jobs);
+        assert_eq!(4, jobs.jobs);
+        assert!(Cli::try_parse_from(["aoc_runner", "--all", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let default = Cli::try_parse_from(["aoc_runner", "--all"]).unwrap();
+        let json = Cli::try_parse_from(["aoc_runner", "--all", "--format", "json"]).unwrap();
+
+        assert_eq!(Format::Text, default.format);
+        assert_eq!(Format::Json, json.format);
+        assert!(Cli::try_parse_from(["aoc_runner", "--all", "--format", "xml"]).is_err());
+    }
+}
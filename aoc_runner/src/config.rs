@@ -0,0 +1,208 @@
+use crate::cli::Cli;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use utils::FileReader;
+
+const DEFAULT_CONFIG: &str = "aoc_runner.toml";
+
+/// Settings read from the runner's config file:
+///
+/// ```toml
+/// input_dir = "../aoc_inputs"
+/// answers = "../aoc_inputs/answers.toml"
+/// timeout = 10
+///
+/// [timeouts.aoc_25]
+/// day_02 = 60
+/// ```
+///
+/// Relative paths are resolved against the directory holding the config file.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub input_dir: Option<PathBuf>,
+    pub answers: Option<PathBuf>,
+    pub timeout: Option<f64>,
+    pub timeouts: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl Config {
+    /// Loads the given config file, or `aoc_runner.toml` from the current
+    /// directory if no file was given and there is one.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG).exists() => Path::new(DEFAULT_CONFIG),
+            None => return Ok(Self::default()),
+        };
+
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+        let config = Self::parse(&contents)
+            .map_err(|err| format!("Unable to parse {}: {}", path.display(), err))?;
+
+        Ok(config.relative_to(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|err| err.to_string())
+    }
+
+    fn relative_to(self, dir: &Path) -> Self {
+        Self {
+            input_dir: self.input_dir.map(|p| dir.join(p)),
+            answers: self.answers.map(|p| dir.join(p)),
+            ..self
+        }
+    }
+}
+
+/// Parses keys like `aoc_25` or `day_01` that identify a year or a day.
+pub fn parse_key<T: FromStr>(key: &str, prefix: &str) -> Result<T, String> {
+    key.strip_prefix(prefix)
+        .and_then(|k| k.parse::<T>().ok())
+        .ok_or_else(|| format!("expected a key like {}01, got \"{}\"", prefix, key))
+}
+
+/// Where the puzzle inputs and recorded answers are read from.
+#[derive(Debug)]
+pub struct Paths {
+    input_dir: PathBuf,
+    input: Option<FileReader>,
+    answers: PathBuf,
+}
+
+impl Paths {
+    /// Command line arguments (or `AOC_INPUT_DIR`) take precedence over the
+    /// config file, which takes precedence over the workspace's `resources`
+    /// directory. An input of `-` is read from stdin straight away so that every
+    /// part can be run against it.
+    pub fn resolve(cli: &Cli, config: &Config) -> Result<Self, String> {
+        let input_dir = cli
+            .input_dir
+            .clone()
+            .or_else(|| config.input_dir.clone())
+            .unwrap_or_else(default_input_dir);
+
+        let answers = cli
+            .answers
+            .clone()
+            .or_else(|| config.answers.clone())
+            .unwrap_or_else(|| input_dir.join("answers.toml"));
+
+        let input = match cli.input() {
+            Some(path) if path == Path::new("-") => {
+                Some(FileReader::stdin().map_err(|err| format!("Unable to read stdin: {}", err))?)
+            }
+            Some(path) => Some(FileReader::new(path)),
+            None => None,
+        };
+
+        Ok(Self {
+            input_dir,
+            input,
+            answers,
+        })
+    }
+
+    pub fn reader(&self, year: u16, day: u8) -> FileReader {
+        match &self.input {
+            Some(input) => input.clone(),
+            None => FileReader::new(
+                self.input_dir
+                    .join(format!("aoc_{}", year))
+                    .join(format!("day_{:02}.txt", day)),
+            ),
+        }
+    }
+
+    pub fn answers(&self) -> &Path {
+        &self.answers
+    }
+}
+
+// Found relative to the runner's own crate so that the default works no matter
+// which directory the binary is launched from.
+fn default_input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("resources")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn cli(args: &[&str]) -> Cli {
+        Cli::try_parse_from(["aoc_runner", "--all"].iter().chain(args)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse("input_dir = \"inputs\"").unwrap();
+
+        assert_eq!(Some(PathBuf::from("inputs")), config.input_dir);
+        assert_eq!(None, config.answers);
+        assert!(Config::parse("inputs = \"inputs\"").is_err());
+    }
+
+    #[test]
+    fn test_config_relative_to_file() {
+        let config = Config::parse("input_dir = \"inputs\"\nanswers = \"/answers.toml\"")
+            .unwrap()
+            .relative_to(Path::new("config"));
+
+        assert_eq!(Some(PathBuf::from("config/inputs")), config.input_dir);
+        assert_eq!(Some(PathBuf::from("/answers.toml")), config.answers);
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let config = Config {
+            input_dir: Some(PathBuf::from("from_config")),
+            ..Config::default()
+        };
+
+        let paths = Paths::resolve(&cli(&["--input-dir", "from_cli"]), &config).unwrap();
+        assert_eq!(
+            FileReader::new("from_cli/aoc_25/day_03.txt"),
+            paths.reader(25, 3)
+        );
+
+        let paths = Paths::resolve(&cli(&[]), &config).unwrap();
+        assert_eq!(
+            FileReader::new("from_config/aoc_25/day_03.txt"),
+            paths.reader(25, 3)
+        );
+        assert_eq!(Path::new("from_config/answers.toml"), paths.answers());
+    }
+
+    #[test]
+    fn test_resolve_default() {
+        let paths = Paths {
+            input_dir: default_input_dir(),
+            input: None,
+            answers: PathBuf::new(),
+        };
+
+        assert_eq!(
+            FileReader::new(default_input_dir().join("aoc_24/day_01.txt")),
+            paths.reader(24, 1)
+        );
+    }
+
+    #[test]
+    fn test_input_override() {
+        let flag = Paths::resolve(&cli(&["--input", "sample.txt"]), &Config::default()).unwrap();
+        let positional = Paths::resolve(&cli(&["sample.txt"]), &Config::default()).unwrap();
+
+        assert_eq!(FileReader::new("sample.txt"), flag.reader(25, 3));
+        assert_eq!(FileReader::new("sample.txt"), positional.reader(25, 3));
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{
+    cli::Cli,
+    config::{self, Config},
+};
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+use utils::{FileReader, Outcome, Part, Puzzle};
+
+const DEFAULT_TIMEOUT_SECS: f64 = 10.0;
+
+/// How long each part may run before it is given up on.
+#[derive(Debug, PartialEq)]
+pub struct Limits {
+    default: Option<Duration>,
+    days: HashMap<(u16, u8), Option<Duration>>,
+}
+
+impl Limits {
+    /// `--timeout` takes precedence over the config file's `timeout`, and the
+    /// config file's per-day `timeouts` take precedence over both. A limit of
+    /// zero means no limit.
+    pub fn resolve(cli: &Cli, config: &Config) -> Result<Self, String> {
+        let default = to_limit(
+            cli.timeout
+                .or(config.timeout)
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+        )?;
+        let mut days = HashMap::new();
+
+        for (year_key, overrides) in &config.timeouts {
+            let year = config::parse_key(year_key, "aoc_")?;
+
+            for (day_key, secs) in overrides {
+                let day = config::parse_key(day_key, "day_")?;
+                days.insert((year, day), to_limit(*secs)?);
+            }
+        }
+
+        Ok(Self { default, days })
+    }
+
+    pub fn for_day(&self, year: u16, day: u8) -> Option<Duration> {
+        self.days.get(&(year, day)).copied().unwrap_or(self.default)
+    }
+}
+
+fn to_limit(secs: f64) -> Result<Option<Duration>, String> {
+    if secs == 0.0 {
+        return Ok(None);
+    }
+
+    Duration::try_from_secs_f64(secs)
+        .map(Some)
+        .map_err(|_| format!("expected a timeout in seconds, got {}", secs))
+}
+
+/// How a part run under a time limit ended.
+#[derive(Debug, PartialEq)]
+pub enum Run {
+    Finished(Outcome),
+    TimedOut(Duration),
+}
+
+/// Parses the day's input once and solves each of `parts` from it, returning
+/// a run for each part in the same order. Parsing and each part are given
+/// `limit` on their own. When parsing fails or runs over, every part ends the
+/// same way.
+pub fn solve_day(
+    puzzle: &Puzzle,
+    reader: FileReader,
+    parts: &[Part],
+    limit: Option<Duration>,
+) -> Vec<Run> {
+    let puzzle = *puzzle;
+    let parsed = within(limit, move || {
+        let start = Instant::now();
+        let parsed = puzzle.parse(reader);
+
+        (parsed, start.elapsed())
+    });
+
+    let input = match parsed {
+        Ok((Ok(input), parse_time)) => (input, parse_time),
+        Ok((Err(err), parse_time)) => {
+            let outcome = Outcome::new(Err(err), parse_time, Duration::ZERO);
+            return parts
+                .iter()
+                .map(|_| Run::Finished(outcome.clone()))
+                .collect();
+        }
+        Err(stopped) => return parts.iter().map(|_| stopped.run()).collect(),
+    };
+
+    parts
+        .iter()
+        .map(|&part| {
+            let (input, parse_time) = input.clone();
+            let solved = within(limit, move || {
+                let start = Instant::now();
+                let answer = puzzle.solve(&input, part);
+
+                (answer, start.elapsed())
+            });
+
+            match solved {
+                Ok((answer, solve_time)) => {
+                    Run::Finished(Outcome::new(answer, parse_time, solve_time))
+                }
+                Err(stopped) => stopped.run(),
+            }
+        })
+        .collect()
+}
+
+// Why a task run by `within` produced nothing.
+#[derive(Clone, Copy)]
+enum Stopped {
+    TimedOut(Duration),
+    Panicked,
+}
+
+impl Stopped {
+    fn run(self) -> Run {
+        match self {
+            Stopped::TimedOut(limit) => Run::TimedOut(limit),
+            Stopped::Panicked => Run::Finished(Outcome {
+                answer: Err(String::from("Solution panicked")),
+                diagnostic: None,
+                parse_time: Duration::ZERO,
+                solve_time: Duration::ZERO,
+            }),
+        }
+    }
+}
+
+// Runs the task on its own thread and stops waiting for it once `limit` has
+// passed. Threads can't be cancelled, so a task that runs over is left to
+// finish in the background and its result is thrown away. That thread isn't
+// one of the `--jobs` workers, so it competes with them for the CPU until it
+// finishes or the run ends. Without a limit the task runs on the calling
+// thread instead. Either way a panic is reported as such rather than taking
+// down the run.
+fn within<T: Send + 'static>(
+    limit: Option<Duration>,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, Stopped> {
+    let Some(limit) = limit else {
+        return panic::catch_unwind(AssertUnwindSafe(task)).map_err(|_| Stopped::Panicked);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        // The receiver is gone if the task timed out, in which case nobody is
+        // waiting for the result anymore.
+        let _ = sender.send(task());
+    });
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => Err(Stopped::TimedOut(limit)),
+        Err(RecvTimeoutError::Disconnected) => Err(Stopped::Panicked),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use utils::{Answer, Error, Registry, Solution};
+
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        type Input = u64;
+
+        fn parse(reader: FileReader) -> Result<Self::Input, Error> {
+            Ok(reader.read_string()?.parse()?)
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer, Error> {
+            thread::sleep(Duration::from_millis(*input));
+            Ok((*input).into())
+        }
+
+        fn part_two(_: &Self::Input) -> Result<Answer, Error> {
+            panic!("part two is broken")
+        }
+    }
+
+    static PUZZLES: [Puzzle; 1] = [Puzzle::new::<Sleepy>(25, 1)];
+
+    fn limits(args: &[&str], config: &str) -> Result<Limits, String> {
+        let cli = Cli::try_parse_from(["aoc_runner", "--all"].iter().chain(args)).unwrap();
+        let config: Config = toml::from_str(config).unwrap();
+
+        Limits::resolve(&cli, &config)
+    }
+
+    #[test]
+    fn test_resolve_limits() {
+        let default = limits(&[], "").unwrap();
+        let config = "timeout = 2\n[timeouts.aoc_25]\nday_02 = 60\nday_03 = 0";
+        let configured = limits(&[], config).unwrap();
+        let overridden = limits(&["--timeout", "0.5"], config).unwrap();
+
+        assert_eq!(Some(Duration::from_secs(10)), default.for_day(25, 1));
+        assert_eq!(Some(Duration::from_secs(2)), configured.for_day(25, 1));
+        assert_eq!(Some(Duration::from_secs(60)), configured.for_day(25, 2));
+        assert_eq!(None, configured.for_day(25, 3));
+        assert_eq!(Some(Duration::from_millis(500)), overridden.for_day(25, 1));
+        assert_eq!(Some(Duration::from_secs(60)), overridden.for_day(25, 2));
+        assert!(limits(&[], "[timeouts.aoc_25]\nfirst = 1").is_err());
+        assert!(limits(&["--timeout=-1"], "").is_err());
+    }
+
+    #[test]
+    fn test_solve_within_limit() {
+        let registry = Registry::new([&PUZZLES[..]]);
+        let puzzle = registry.find(25, 1).unwrap();
+        let limit = Some(Duration::from_millis(200));
+
+        let [Run::Finished(outcome)] =
+            &solve_day(puzzle, FileReader::from_text("1"), &[Part::One], limit)[..]
+        else {
+            panic!("expected the part to finish");
+        };
+        assert_eq!(Ok(Answer::from(1)), outcome.answer);
+
+        assert_eq!(
+            vec![Run::TimedOut(Duration::from_millis(200))],
+            solve_day(puzzle, FileReader::from_text("2000"), &[Part::One], limit)
+        );
+    }
+
+    #[test]
+    fn test_solve_day_shares_parse() {
+        let registry = Registry::new([&PUZZLES[..]]);
+        let puzzle = registry.find(25, 1).unwrap();
+        let limit = Some(Duration::from_secs(5));
+
+        let runs = solve_day(
+            puzzle,
+            FileReader::from_text("1"),
+            &[Part::One, Part::Two],
+            limit,
+        );
+        let [Run::Finished(one), Run::Finished(two)] = &runs[..] else {
+            panic!("expected both parts to finish");
+        };
+        assert_eq!(Ok(Answer::from(1)), one.answer);
+        assert_eq!(Err(String::from("Solution panicked")), two.answer);
+
+        let runs = solve_day(
+            puzzle,
+            FileReader::from_text("1"),
+            &[Part::One, Part::Two],
+            None,
+        );
+        let [Run::Finished(one), Run::Finished(two)] = &runs[..] else {
+            panic!("expected both parts to finish without a limit");
+        };
+        assert_eq!(Ok(Answer::from(1)), one.answer);
+        assert_eq!(Err(String::from("Solution panicked")), two.answer);
+
+        let runs = solve_day(
+            puzzle,
+            FileReader::from_text("x"),
+            &[Part::One, Part::Two],
+            None,
+        );
+        assert_eq!(2, runs.len());
+        assert!(runs.iter().all(|run| matches!(
+            run,
+            Run::Finished(Outcome { answer: Err(msg), solve_time: Duration::ZERO, .. })
+                if msg.starts_with("year 25, day 01, <input>: ")
+        )));
+    }
+}
@@ -1,7 +1,208 @@
-use aoc_24::AoC24Processor;
-use aoc_25::AoC25Processor;
+mod bench;
+mod cli;
+mod config;
+mod limit;
+mod pool;
+mod report;
+mod verify;
 
-fn main() {
-    AoC24Processor::process();
-    AoC25Processor::process();
+use bench::BenchRecord;
+use clap::Parser;
+use cli::Cli;
+use config::{Config, Paths};
+use limit::{Limits, Run};
+use report::{Format, Record, Status};
+use serde::Serialize;
+use std::{fmt::Display, io, process::ExitCode};
+use utils::{Entry, Part, Puzzle, Registry};
+use verify::{Answers, VerifyRecord};
+
+fn registry() -> Registry {
+    Registry::new([aoc_24::PUZZLES, aoc_25::PUZZLES])
+}
+
+// Parses the day's input once, solves each requested part from it and records
+// the answers or the reasons they failed. The entries all belong to `puzzle`.
+fn run(paths: &Paths, limits: &Limits, puzzle: &Puzzle, entries: &[Entry]) -> Vec<Record> {
+    let parts: Vec<Part> = entries.iter().map(|entry| entry.part).collect();
+
+    let mut runs = limit::solve_day(
+        puzzle,
+        paths.reader(puzzle.year, puzzle.day),
+        &parts,
+        limits.for_day(puzzle.year, puzzle.day),
+    )
+    .into_iter();
+
+    entries
+        .iter()
+        .map(|entry| match runs.next() {
+            Some(Run::Finished(outcome)) => Record::from_outcome(entry, outcome),
+            Some(Run::TimedOut(limit)) => Record::timed_out(entry, limit),
+            None => unreachable!("a run is returned for every part"),
+        })
+        .collect()
+}
+
+fn list(entries: &[Entry]) {
+    for entry in entries {
+        println!(
+            "AoC {} Day {:02} Part {}",
+            entry.year, entry.day, entry.part
+        );
+    }
+}
+
+// Runs every day's entries on up to `jobs` threads, one day per task, keeping
+// the results in entry order.
+fn collect<R: Send>(
+    registry: &Registry,
+    entries: &[Entry],
+    jobs: u32,
+    run: impl Fn(&Puzzle, &[Entry]) -> Vec<R> + Sync,
+) -> Vec<R> {
+    let tasks: Vec<(&Puzzle, &[Entry])> = entries
+        .chunk_by(|a, b| (a.year, a.day) == (b.year, b.day))
+        .filter_map(|day| {
+            registry
+                .find(day[0].year, day[0].day)
+                .map(|puzzle| (puzzle, day))
+        })
+        .collect();
+
+    pool::map(&tasks, jobs as usize, |(puzzle, day)| run(puzzle, day))
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+// Writes the records and exits with a failure if they could not be written or
+// if any of them represents a failure.
+fn write<R: Display + Serialize>(
+    cli: &Cli,
+    records: &[R],
+    failed: impl Fn(&R) -> bool,
+) -> ExitCode {
+    let written = report::write_records(cli.format, records, &mut io::stdout().lock());
+
+    exit_code(written, records.iter().any(failed))
+}
+
+fn exit_code(written: io::Result<()>, failed: bool) -> ExitCode {
+    if let Err(err) = written {
+        eprintln!("Failed to write results: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    if failed {
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn verify(
+    cli: &Cli,
+    paths: &Paths,
+    limits: &Limits,
+    registry: &Registry,
+    entries: &[Entry],
+) -> ExitCode {
+    let answers = match Answers::load(paths.answers()) {
+        Ok(answers) => answers,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let records: Vec<VerifyRecord> = collect(registry, entries, cli.jobs, |puzzle, day| {
+        run(paths, limits, puzzle, day)
+            .into_iter()
+            .zip(day)
+            .map(|(record, entry)| VerifyRecord::new(record, answers.get(entry)))
+            .collect()
+    });
+
+    if cli.format != Format::Text {
+        return write(cli, &records, VerifyRecord::is_failure);
+    }
+
+    let written = verify::write_table(&records, &mut io::stdout().lock());
+
+    exit_code(written, records.iter().any(VerifyRecord::is_failure))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let selection = cli.selection();
+    let registry = registry();
+
+    let entries: Vec<Entry> = registry
+        .entries()
+        .filter(|entry| selection.includes(entry))
+        .collect();
+
+    if entries.is_empty() {
+        eprintln!("No solutions match the requested year, day and part");
+        return ExitCode::FAILURE;
+    }
+
+    // An input file belongs to a single day, so running it against several
+    // would only produce failures or nonsense answers.
+    if cli.input().is_some()
+        && entries
+            .iter()
+            .any(|e| (e.year, e.day) != (entries[0].year, entries[0].day))
+    {
+        eprintln!("--input can only be used when a single day is selected");
+        return ExitCode::FAILURE;
+    }
+
+    if cli.list {
+        list(&entries);
+        return ExitCode::SUCCESS;
+    }
+
+    let settings = Config::load(cli.config.as_deref()).and_then(|config| {
+        Ok((
+            Paths::resolve(&cli, &config)?,
+            Limits::resolve(&cli, &config)?,
+        ))
+    });
+    let (paths, limits) = match settings {
+        Ok(settings) => settings,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if cli.bench {
+        let records: Vec<BenchRecord> = collect(&registry, &entries, cli.jobs, |puzzle, day| {
+            day.iter()
+                .map(|entry| {
+                    bench::bench(
+                        puzzle,
+                        entry,
+                        || paths.reader(puzzle.year, puzzle.day),
+                        limits.for_day(puzzle.year, puzzle.day),
+                        cli.warmup as usize,
+                        cli.iterations as usize,
+                    )
+                })
+                .collect()
+        });
+
+        return write(&cli, &records, |r| r.status == Status::Failed);
+    }
+
+    if cli.verify {
+        return verify(&cli, &paths, &limits, &registry, &entries);
+    }
+
+    let records: Vec<Record> = collect(&registry, &entries, cli.jobs, |puzzle, day| {
+        run(&paths, &limits, puzzle, day)
+    });
+    write(&cli, &records, |r| r.status == Status::Failed)
 }
@@ -0,0 +1,73 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// Runs `task` over every item on up to `jobs` threads and returns the results
+/// in the same order as the items, however the work ended up being scheduled.
+pub fn map<T, R>(items: &[T], jobs: usize, task: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 {
+        return items.iter().map(task).collect();
+    }
+
+    // Workers take the next unclaimed item until none are left, so a slow day
+    // only holds up its own thread.
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut results = vec![];
+
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(item) = items.get(index) else {
+                return results;
+            };
+
+            results.push((index, task(item)));
+        }
+    };
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(items.len()))
+            .map(|_| scope.spawn(worker))
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+
+        // Earlier items take longer so they would finish last if the results
+        // were kept in completion order.
+        let results = map(&items, 4, |i| {
+            thread::sleep(Duration::from_millis(20 - i));
+            i * 2
+        });
+
+        assert_eq!(items.iter().map(|i| i * 2).collect::<Vec<_>>(), results);
+    }
+
+    #[test]
+    fn test_map_sequential_and_empty() {
+        assert_eq!(vec![2, 3], map(&[1, 2], 1, |i| i + 1));
+        assert_eq!(Vec::<i32>::new(), map(&[], 8, |i: &i32| i + 1));
+    }
+}
@@ -0,0 +1,259 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    fmt,
+    io::{self, Write},
+    time::Duration,
+};
+use utils::{Answer, Entry, Outcome};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Failed,
+    #[serde(rename = "timed out")]
+    TimedOut,
+}
+
+/// The outcome of running a single part of a single day.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub status: Status,
+    pub error: Option<String>,
+    /// Where in the input the error came from, shown beneath text output.
+    #[serde(skip)]
+    pub diagnostic: Option<String>,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+    pub elapsed_ms: f64,
+}
+
+impl Record {
+    pub fn from_outcome(entry: &Entry, outcome: Outcome) -> Self {
+        let (answer, status, error) = match outcome.answer {
+            Ok(answer) => (Some(answer), Status::Ok, None),
+            Err(error) => (None, Status::Failed, Some(error)),
+        };
+
+        Self {
+            year: entry.year,
+            day: entry.day,
+            part: entry.part.number(),
+            answer,
+            status,
+            error,
+            diagnostic: outcome.diagnostic,
+            parse_ms: millis(outcome.parse_time),
+            solve_ms: millis(outcome.solve_time),
+            elapsed_ms: millis(outcome.parse_time + outcome.solve_time),
+        }
+    }
+
+    pub fn timed_out(entry: &Entry, limit: Duration) -> Self {
+        Self {
+            year: entry.year,
+            day: entry.day,
+            part: entry.part.number(),
+            answer: None,
+            status: Status::TimedOut,
+            error: None,
+            diagnostic: None,
+            parse_ms: 0.0,
+            solve_ms: 0.0,
+            elapsed_ms: millis(limit),
+        }
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", label(self.year, self.day, self.part))?;
+
+        match self.status {
+            Status::Ok => write!(
+                f,
+                "{} (parse {:.3} ms, solve {:.3} ms)",
+                self.answer
+                    .as_ref()
+                    .map(Answer::to_string)
+                    .unwrap_or_default(),
+                self.parse_ms,
+                self.solve_ms
+            ),
+            Status::Failed => {
+                write!(
+                    f,
+                    "Failed with message: {}",
+                    self.error.as_deref().unwrap_or_default()
+                )?;
+
+                match &self.diagnostic {
+                    Some(diagnostic) => write!(f, "\n{}", diagnostic),
+                    None => Ok(()),
+                }
+            }
+            Status::TimedOut => write!(f, "Timed out after {} ms", self.elapsed_ms),
+        }
+    }
+}
+
+pub fn label(year: u16, day: u8, part: u8) -> String {
+    format!("AoC {} Day {:02} Part {}", year, day, part)
+}
+
+// Milliseconds to microsecond precision, which keeps the printed value free of
+// floating point noise.
+pub fn millis(elapsed: Duration) -> f64 {
+    elapsed.as_micros() as f64 / 1000.0
+}
+
+/// Writes one line per record for text output, or the serialized records for
+/// the machine-readable formats.
+pub fn write_records<R>(format: Format, records: &[R], out: &mut impl Write) -> io::Result<()>
+where
+    R: fmt::Display + Serialize,
+{
+    match format {
+        Format::Text => write_text(records, out),
+        Format::Json => write_json(records, out),
+        Format::Csv => write_csv(records, out),
+    }
+}
+
+fn write_text<R: fmt::Display>(records: &[R], out: &mut impl Write) -> io::Result<()> {
+    for record in records {
+        writeln!(out, "{}", record)?;
+    }
+
+    Ok(())
+}
+
+fn write_json<R: Serialize>(records: &[R], out: &mut impl Write) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, records)?;
+    writeln!(out)
+}
+
+fn write_csv<R: Serialize>(records: &[R], out: &mut impl Write) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+
+    for record in records {
+        writer.serialize(record).map_err(io::Error::other)?;
+    }
+
+    writer.flush()
+}
+
+/// Builds an entry for the runner's tests.
+#[cfg(test)]
+pub fn entry(year: u16, day: u8, part: utils::Part) -> Entry {
+    Entry { year, day, part }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Part;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::from_outcome(
+                &entry(25, 3, Part::One),
+                Outcome {
+                    answer: Ok(Answer::from(357)),
+                    diagnostic: None,
+                    parse_time: Duration::from_micros(500),
+                    solve_time: Duration::from_micros(1000),
+                },
+            ),
+            Record::from_outcome(
+                &entry(25, 3, Part::Two),
+                Outcome {
+                    answer: Err(String::from("Invalid Input")),
+                    diagnostic: Some(String::from(" --> day_03.txt:1:1")),
+                    parse_time: Duration::from_micros(250),
+                    solve_time: Duration::ZERO,
+                },
+            ),
+            Record::timed_out(&entry(25, 2, Part::One), Duration::from_secs(10)),
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut out = vec![];
+        write_records(format, &records(), &mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write_text() {
+        let expected = "AoC 25 Day 03 Part 1: 357 (parse 0.500 ms, solve 1.000 ms)\n\
+                        AoC 25 Day 03 Part 2: Failed with message: Invalid Input\n \
+                        --> day_03.txt:1:1\n\
+                        AoC 25 Day 02 Part 1: Timed out after 10000 ms\n";
+
+        assert_eq!(expected, render(Format::Text));
+    }
+
+    #[test]
+    fn test_write_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json)).unwrap();
+
+        assert_eq!(3, json.as_array().unwrap().len());
+        assert_eq!(357, json[0]["answer"]);
+        assert_eq!("ok", json[0]["status"]);
+        assert_eq!(0.5, json[0]["parse_ms"]);
+        assert_eq!(1.0, json[0]["solve_ms"]);
+        assert_eq!(1.5, json[0]["elapsed_ms"]);
+        assert_eq!("failed", json[1]["status"]);
+        assert_eq!("Invalid Input", json[1]["error"]);
+        assert_eq!(serde_json::Value::Null, json[1]["diagnostic"]);
+        assert_eq!(serde_json::Value::Null, json[2]["answer"]);
+        assert_eq!("timed out", json[2]["status"]);
+    }
+
+    #[test]
+    fn test_write_json_large_answer() {
+        let record = Record::from_outcome(
+            &entry(25, 3, Part::Two),
+            Outcome {
+                answer: Ok(Answer::from(u128::MAX)),
+                diagnostic: None,
+                parse_time: Duration::ZERO,
+                solve_time: Duration::ZERO,
+            },
+        );
+        let mut out = vec![];
+        write_records(Format::Json, &[record], &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        assert_eq!(u128::MAX.to_string(), json[0]["answer"]);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let csv = render(Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            "year,day,part,answer,status,error,parse_ms,solve_ms,elapsed_ms",
+            lines[0]
+        );
+        assert_eq!("25,3,1,357,ok,,0.5,1.0,1.5", lines[1]);
+        assert_eq!("25,3,2,,failed,Invalid Input,0.25,0.0,0.25", lines[2]);
+        assert_eq!("25,2,1,,timed out,,0.0,0.0,10000.0", lines[3]);
+    }
+}
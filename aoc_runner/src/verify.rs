@@ -0,0 +1,322 @@
+use crate::{
+    config::parse_key,
+    report::{Record, Status},
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    io::{self, Write},
+    path::Path,
+};
+use utils::{Answer, Entry, Part};
+
+/// Answers recorded in a TOML file, keyed by year, day and part:
+///
+/// ```toml
+/// [aoc_25.day_01]
+/// part_1 = 1092
+/// part_2 = "6616"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(HashMap<Entry, Answer>);
+
+type AnswersFile = BTreeMap<String, BTreeMap<String, BTreeMap<String, toml::Value>>>;
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+
+        Self::parse(&contents).map_err(|err| format!("Unable to parse {}: {}", path.display(), err))
+    }
+
+    fn parse(contents: &str) -> Result<Self, String> {
+        let file: AnswersFile = toml::from_str(contents).map_err(|err| err.to_string())?;
+        let mut answers = HashMap::new();
+
+        for (year_key, days) in file {
+            let year = parse_key(&year_key, "aoc_")?;
+
+            for (day_key, parts) in days {
+                let day = parse_key(&day_key, "day_")?;
+
+                for (part_key, value) in parts {
+                    let part = part_key
+                        .strip_prefix("part_")
+                        .and_then(|p| p.parse::<Part>().ok())
+                        .ok_or_else(|| {
+                            format!("expected part_1 or part_2, got \"{}\"", part_key)
+                        })?;
+
+                    let answer = match value {
+                        toml::Value::Integer(i) => Answer::from(i),
+                        toml::Value::String(s) => Answer::parse(&s),
+                        other => {
+                            return Err(format!(
+                                "expected an integer or string answer for {}.{}.{}, got {}",
+                                year_key, day_key, part_key, other
+                            ))
+                        }
+                    };
+
+                    answers.insert(Entry { year, day, part }, answer);
+                }
+            }
+        }
+
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, entry: &Entry) -> Option<&Answer> {
+        self.0.get(entry)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Mismatch,
+    Failed,
+    TimedOut,
+    Unrecorded,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch | Verdict::Failed)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Pass => "pass",
+            Verdict::Mismatch => "FAIL",
+            Verdict::Failed => "ERROR",
+            Verdict::TimedOut => "timed out",
+            Verdict::Unrecorded => "unrecorded",
+        };
+
+        f.pad(verdict)
+    }
+}
+
+/// A computed answer compared against the recorded one.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct VerifyRecord {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub expected: Option<Answer>,
+    pub actual: Option<Answer>,
+    pub error: Option<String>,
+}
+
+impl VerifyRecord {
+    pub fn new(record: Record, expected: Option<&Answer>) -> Self {
+        let verdict = match (record.status, expected, record.answer.as_ref()) {
+            (Status::TimedOut, _, _) => Verdict::TimedOut,
+            (Status::Failed, _, _) => Verdict::Failed,
+            (Status::Ok, None, _) => Verdict::Unrecorded,
+            (Status::Ok, Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+            (Status::Ok, Some(_), _) => Verdict::Mismatch,
+        };
+
+        Self {
+            year: record.year,
+            day: record.day,
+            part: record.part,
+            verdict,
+            expected: expected.cloned(),
+            actual: record.answer,
+            error: record.error,
+        }
+    }
+}
+
+impl VerifyRecord {
+    /// Whether this record should fail the run. A part that times out fails
+    /// when there is an answer it should have reached, as it may have started
+    /// hanging.
+    pub fn is_failure(&self) -> bool {
+        self.verdict.is_failure() || (self.verdict == Verdict::TimedOut && self.expected.is_some())
+    }
+}
+
+impl fmt::Display for VerifyRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<4} {:<3} {:<4} {:<10} {:<20} {}",
+            self.year,
+            format!("{:02}", self.day),
+            self.part,
+            self.verdict,
+            self.expected
+                .as_ref()
+                .map_or(String::from("-"), Answer::to_string),
+            match (&self.actual, &self.error) {
+                (Some(actual), _) => actual.to_string(),
+                (None, Some(error)) => error.clone(),
+                (None, None) => String::from("-"),
+            }
+        )
+    }
+}
+
+/// Writes the verification results as a table followed by a summary line.
+pub fn write_table(records: &[VerifyRecord], out: &mut impl Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<4} {:<3} {:<4} {:<10} {:<20} Actual",
+        "Year", "Day", "Part", "Result", "Expected"
+    )?;
+
+    for record in records {
+        writeln!(out, "{}", record)?;
+    }
+
+    let count = |verdict: Verdict| records.iter().filter(|r| r.verdict == verdict).count();
+    writeln!(
+        out,
+        "\n{} passed, {} mismatched, {} failed, {} timed out, {} unrecorded",
+        count(Verdict::Pass),
+        count(Verdict::Mismatch),
+        count(Verdict::Failed),
+        count(Verdict::TimedOut),
+        count(Verdict::Unrecorded)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::entry;
+    use std::time::Duration;
+    use utils::Outcome;
+
+    fn solved(entry: &Entry, answer: Result<&str, &str>) -> Record {
+        Record::from_outcome(
+            entry,
+            Outcome {
+                answer: answer.map(Answer::parse).map_err(String::from),
+                diagnostic: None,
+                parse_time: Duration::ZERO,
+                solve_time: Duration::ZERO,
+            },
+        )
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(
+            r#"
+            [aoc_25.day_01]
+            part_1 = 1092
+            part_2 = "6616"
+
+            [aoc_24.day_02]
+            part_1 = 2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(&Answer::from(1092)),
+            answers.get(&entry(25, 1, Part::One))
+        );
+        assert_eq!(
+            Some(&Answer::from(6616)),
+            answers.get(&entry(25, 1, Part::Two))
+        );
+        assert_eq!(
+            Some(&Answer::from(2)),
+            answers.get(&Entry {
+                year: 24,
+                day: 2,
+                part: Part::One
+            })
+        );
+        assert_eq!(None, answers.get(&entry(25, 2, Part::One)));
+    }
+
+    #[test]
+    fn test_parse_answers_invalid_keys() {
+        assert!(Answers::parse("[aoc_25.day_01]\npart_3 = 1").is_err());
+        assert!(Answers::parse("[aoc_25.first]\npart_1 = 1").is_err());
+        assert!(Answers::parse("[year.day_01]\npart_1 = 1").is_err());
+        assert!(Answers::parse("[aoc_25.day_01]\npart_1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let one = entry(25, 1, Part::One);
+        let three = Answer::from(3);
+
+        assert_eq!(
+            Verdict::Pass,
+            VerifyRecord::new(solved(&one, Ok("3")), Some(&three)).verdict
+        );
+        assert_eq!(
+            Verdict::Mismatch,
+            VerifyRecord::new(solved(&one, Ok("4")), Some(&three)).verdict
+        );
+        assert_eq!(
+            Verdict::Failed,
+            VerifyRecord::new(solved(&one, Err("Invalid Input")), Some(&three)).verdict
+        );
+        assert_eq!(
+            Verdict::Unrecorded,
+            VerifyRecord::new(solved(&one, Ok("3")), None).verdict
+        );
+        assert_eq!(
+            Verdict::TimedOut,
+            VerifyRecord::new(
+                Record::timed_out(&one, Duration::from_secs(1)),
+                Some(&three)
+            )
+            .verdict
+        );
+    }
+
+    #[test]
+    fn test_timed_out_failures() {
+        let one = entry(25, 1, Part::One);
+        let timed_out = || Record::timed_out(&one, Duration::from_secs(1));
+
+        assert!(VerifyRecord::new(timed_out(), Some(&Answer::from(3))).is_failure());
+        assert!(!VerifyRecord::new(timed_out(), None).is_failure());
+        assert!(VerifyRecord::new(solved(&one, Ok("4")), Some(&Answer::from(3))).is_failure());
+        assert!(!VerifyRecord::new(solved(&one, Ok("4")), None).is_failure());
+    }
+
+    #[test]
+    fn test_write_table() {
+        let three = Answer::from(3);
+        let records = vec![
+            VerifyRecord::new(solved(&entry(25, 1, Part::One), Ok("3")), Some(&three)),
+            VerifyRecord::new(
+                solved(&entry(25, 1, Part::Two), Ok("7")),
+                Some(&Answer::from(6)),
+            ),
+        ];
+        let mut out = vec![];
+        write_table(&records, &mut out).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            "Year Day Part Result     Expected             Actual",
+            lines[0]
+        );
+        assert_eq!("25   01  1    pass       3                    3", lines[1]);
+        assert_eq!("25   01  2    FAIL       6                    7", lines[2]);
+        assert_eq!(
+            "1 passed, 1 mismatched, 0 failed, 0 timed out, 0 unrecorded",
+            lines[4]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Feeds every registered puzzle random and mangled input, checking that
+//! parsing and solving only ever fail with an error, never a panic.
+//!
+//! The input is generated from a fixed seed so that any failure can be
+//! reproduced.
+
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+};
+use utils::{FileReader, Part, Puzzle, Registry};
+
+const CASES: usize = 200;
+
+const TOKENS: &[&str] = &[
+    "L", "R", "S", "^", ".", "@", "*", "+", "-", ",", "=", " ", "   ", "\n", "\n\n", "\r\n", "\t",
+    "é", "X",
+];
+
+// A xorshift generator, which is plenty for picking input and keeps the tests
+// free of extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+fn random_bytes(rng: &mut Rng) -> Vec<u8> {
+    (0..rng.below(256)).map(|_| rng.next() as u8).collect()
+}
+
+fn number(rng: &mut Rng) -> String {
+    let digits = if rng.below(8) == 0 {
+        1 + rng.below(300)
+    } else {
+        1 + rng.below(4)
+    };
+
+    let mut number = (1 + rng.below(9)).to_string();
+    for _ in 1..digits {
+        number += &rng.below(10).to_string();
+    }
+
+    number
+}
+
+// Text made from the characters the puzzles look for, so more of it gets past
+// the first check in each parser.
+fn random_tokens(rng: &mut Rng) -> Vec<u8> {
+    let mut text = String::new();
+
+    for _ in 0..rng.below(128) {
+        if rng.below(3) == 0 {
+            text += &number(rng);
+        } else {
+            text += *rng.pick(TOKENS);
+        }
+    }
+
+    text.into_bytes()
+}
+
+// Lines of nothing but digits, long enough to overflow anything that counts
+// positions along a line in a small integer.
+fn digit_lines(rng: &mut Rng) -> Vec<u8> {
+    let mut text = String::new();
+
+    for _ in 0..1 + rng.below(8) {
+        for _ in 0..rng.below(300) {
+            text += &rng.below(10).to_string();
+        }
+        text += "\n";
+    }
+
+    text.into_bytes()
+}
+
+// Truncates, overwrites and shuffles the lines of a sample input.
+fn mutate(rng: &mut Rng, sample: &[u8]) -> Vec<u8> {
+    let mut bytes = sample.to_vec();
+
+    for _ in 0..1 + rng.below(4) {
+        if bytes.is_empty() {
+            break;
+        }
+
+        match rng.below(4) {
+            0 => bytes.truncate(rng.below(bytes.len())),
+            1 => {
+                let index = rng.below(bytes.len());
+                bytes[index] = rng.next() as u8;
+            }
+            2 => {
+                let token = rng.pick(TOKENS).as_bytes();
+                let index = rng.below(bytes.len());
+                bytes.splice(index..index, token.iter().copied());
+            }
+            _ => {
+                let mut lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+                let line = *rng.pick(&lines);
+                let index = rng.below(lines.len());
+                if rng.below(2) == 0 {
+                    lines.insert(index, line);
+                } else {
+                    lines.remove(index);
+                }
+                bytes = lines.join(&b'\n');
+            }
+        }
+    }
+
+    bytes
+}
+
+fn samples(puzzle: &Puzzle) -> Vec<Vec<u8>> {
+    let path = format!(
+        "../test-resources/aoc_{}/day_{:02}_sample.txt",
+        puzzle.year % 100,
+        puzzle.day
+    );
+
+    fs::read(path).into_iter().collect()
+}
+
+// Runs the puzzle from start to finish, returning whether anything panicked.
+fn panics(puzzle: &Puzzle, input: &[u8]) -> bool {
+    let run = || {
+        let Ok(reader) = FileReader::from_reader(input) else {
+            return;
+        };
+        let Ok(parsed) = puzzle.parse(reader) else {
+            return;
+        };
+
+        for part in [Part::One, Part::Two] {
+            let _ = puzzle.solve(&parsed, part);
+        }
+    };
+
+    panic::catch_unwind(AssertUnwindSafe(run)).is_err()
+}
+
+#[test]
+fn arbitrary_input_never_panics() {
+    let registry = Registry::new([aoc_24::PUZZLES, aoc_25::PUZZLES]);
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let mut failures = vec![];
+
+    for puzzle in registry.puzzles() {
+        let samples = samples(puzzle);
+        let mut inputs = vec![vec![]];
+
+        for _ in 0..CASES {
+            inputs.push(random_bytes(&mut rng));
+            inputs.push(random_tokens(&mut rng));
+            inputs.push(digit_lines(&mut rng));
+
+            if !samples.is_empty() {
+                let sample = rng.pick(&samples);
+                inputs.push(mutate(&mut rng, sample));
+            }
+        }
+
+        for input in inputs {
+            if panics(puzzle, &input) {
+                failures.push(format!(
+                    "{} day {:02}: {:?}",
+                    puzzle.year,
+                    puzzle.day,
+                    String::from_utf8_lossy(&input)
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "panicked on:\n{}", failures.join("\n"));
+}
@@ -0,0 +1,159 @@
+use num_bigint::BigInt;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// The answer to a part. Integers of any width or sign, including ones too big
+/// for 128 bits, and text all display, compare and serialize the same way, so
+/// `Answer::from(7u8)` equals `Answer::from(7i64)`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Answer(Value);
+
+// Integers are kept in the first of `Signed`, `Unsigned` and `Big` that can
+// hold them, which keeps equal numbers equal whatever type they came from.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Value {
+    Signed(i128),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+}
+
+impl Answer {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self(Value::Text(text.into()))
+    }
+
+    /// Reads an integer when the text is one written the usual way, and keeps
+    /// anything else, such as `"007"` or `"ABC"`, as text.
+    pub fn parse(text: &str) -> Self {
+        match text.parse::<BigInt>() {
+            Ok(n) if n.to_string() == text => Self::from(n),
+            _ => Self::text(text),
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        !matches!(self.0, Value::Text(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Value::Signed(n) => fmt::Display::fmt(n, f),
+            Value::Unsigned(n) => fmt::Display::fmt(n, f),
+            Value::Big(n) => fmt::Display::fmt(n, f),
+            Value::Text(text) => f.pad(text),
+        }
+    }
+}
+
+/// Integers that fit in 64 bits serialize as numbers. Anything bigger is
+/// serialized as a string of digits, since most readers of JSON and CSV can't
+/// hold it as a number without losing precision.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Value::Signed(n) => match i64::try_from(*n) {
+                Ok(n) => serializer.serialize_i64(n),
+                Err(_) => match u64::try_from(*n) {
+                    Ok(n) => serializer.serialize_u64(n),
+                    Err(_) => serializer.collect_str(n),
+                },
+            },
+            Value::Unsigned(n) => serializer.collect_str(n),
+            Value::Big(n) => serializer.collect_str(n),
+            Value::Text(text) => serializer.serialize_str(text),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        if let Ok(n) = i128::try_from(&n) {
+            return Self(Value::Signed(n));
+        }
+
+        match u128::try_from(&n) {
+            Ok(n) => Self(Value::Unsigned(n)),
+            Err(_) => Self(Value::Big(n)),
+        }
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Self(Value::Signed(n)),
+            Err(_) => Self(Value::Unsigned(n)),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+macro_rules! from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Self(Value::Signed(n as i128))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers_are_equal_across_types() {
+        assert_eq!(Answer::from(7u8), Answer::from(7i64));
+        assert_eq!(Answer::from(7u128), Answer::from(BigInt::from(7)));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::from(BigInt::from(u128::MAX))
+        );
+        assert_ne!(Answer::from(7), Answer::text("7"));
+    }
+
+    #[test]
+    fn test_display() {
+        let big = BigInt::from(u128::MAX) * 10;
+
+        assert_eq!("-12", Answer::from(-12i16).to_string());
+        assert_eq!(
+            "3402823669209384634633746074317682114550",
+            Answer::from(big).to_string()
+        );
+        assert_eq!("ABC  |", format!("{:<5}|", Answer::text("ABC")));
+        assert_eq!("   42|", format!("{:>5}|", Answer::from(42)));
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = Answer::parse;
+
+        assert_eq!(Answer::from(1092), parse("1092"));
+        assert_eq!(Answer::from(-5), parse("-5"));
+        assert_eq!(Answer::from(u128::MAX), parse(&u128::MAX.to_string()));
+        assert_eq!(Answer::text("007"), parse("007"));
+        assert_eq!(Answer::text("+5"), parse("+5"));
+        assert_eq!(Answer::text("ABC"), parse("ABC"));
+        assert!(!parse("ABC").is_integer());
+    }
+}
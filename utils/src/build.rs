@@ -0,0 +1,161 @@
+//! Build script support for the solution crates.
+//!
+//! Each year's crate calls `generate_days` from its `build.rs`. Every
+//! `src/day_NN.rs` file has its `DayNN` solution re-exported, and a `PUZZLES`
+//! registry is written to `$OUT_DIR/days.rs` for the crate to `include!`.
+//!
+//! The day modules themselves are declared in `src/lib.rs` as plain
+//! `pub mod day_NN;` items, so that `cargo fmt` and other tools can see them.
+//! The build fails if a day file isn't declared there, so adding a day means
+//! adding the file and that one line.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+pub fn generate_days() -> io::Result<()> {
+    let manifest_dir = PathBuf::from(env_var("CARGO_MANIFEST_DIR")?);
+    let package = env_var("CARGO_PKG_NAME")?;
+    let year = year_from_package(&package).ok_or_else(|| {
+        invalid_data(format!(
+            "expected a package named like aoc_25, got \"{}\"",
+            package
+        ))
+    })?;
+
+    let src_dir = manifest_dir.join("src");
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+
+    let days = find_days(&src_dir)?;
+    let lib = fs::read_to_string(src_dir.join("lib.rs"))?;
+    let undeclared = undeclared_days(&lib, &days);
+    if !undeclared.is_empty() {
+        let declarations: Vec<String> = undeclared
+            .iter()
+            .map(|day| format!("pub mod day_{:02};", day))
+            .collect();
+
+        return Err(invalid_data(format!(
+            "expected src/lib.rs to declare every day, add: {}",
+            declarations.join(" ")
+        )));
+    }
+
+    let out_file = PathBuf::from(env_var("OUT_DIR")?).join("days.rs");
+
+    fs::write(out_file, render_days(year, &days))
+}
+
+fn env_var(key: &str) -> io::Result<String> {
+    env::var(key).map_err(|_| invalid_data(format!("{} is not set", key)))
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn year_from_package(package: &str) -> Option<u16> {
+    package.strip_prefix("aoc_")?.parse::<u16>().ok()
+}
+
+// Finds every `day_NN.rs` file in the directory and returns the day numbers
+// in order.
+fn find_days(src_dir: &Path) -> io::Result<Vec<u8>> {
+    let mut days = vec![];
+
+    for entry in fs::read_dir(src_dir)? {
+        let file_name = entry?.file_name();
+        if let Some(day) = file_name.to_str().and_then(day_from_file_name) {
+            days.push(day);
+        }
+    }
+
+    days.sort();
+    Ok(days)
+}
+
+fn day_from_file_name(file_name: &str) -> Option<u8> {
+    let digits = file_name.strip_prefix("day_")?.strip_suffix(".rs")?;
+
+    if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse::<u8>().ok()
+}
+
+// The days with no `mod day_NN;` line in the crate's `lib.rs`.
+fn undeclared_days(lib: &str, days: &[u8]) -> Vec<u8> {
+    days.iter()
+        .copied()
+        .filter(|day| {
+            let declaration = format!("mod day_{:02};", day);
+
+            !lib.lines().any(|line| {
+                let line = line.trim();
+                line == declaration || line.strip_prefix("pub ") == Some(declaration.as_str())
+            })
+        })
+        .collect()
+}
+
+fn render_days(year: u16, days: &[u8]) -> String {
+    let mut out = String::new();
+
+    for day in days {
+        out.push_str(&format!("pub use day_{:02}::Day{:02};\n", day, day));
+    }
+
+    out.push_str(&format!("\npub const YEAR: u16 = {};\n", year));
+    out.push_str("\n/// Every day solved for this year, in day order.\n");
+    out.push_str("pub static PUZZLES: &[utils::Puzzle] = &[\n");
+    for day in days {
+        out.push_str(&format!(
+            "    utils::Puzzle::new::<Day{:02}>(YEAR, {}),\n",
+            day, day
+        ));
+    }
+    out.push_str("];\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_year_from_package() {
+        assert_eq!(Some(25), year_from_package("aoc_25"));
+        assert_eq!(None, year_from_package("aoc_runner"));
+        assert_eq!(None, year_from_package("utils"));
+    }
+
+    #[test]
+    fn test_day_from_file_name() {
+        assert_eq!(Some(7), day_from_file_name("day_07.rs"));
+        assert_eq!(Some(12), day_from_file_name("day_12.rs"));
+        assert_eq!(None, day_from_file_name("day_7.rs"));
+        assert_eq!(None, day_from_file_name("day_07.txt"));
+        assert_eq!(None, day_from_file_name("error.rs"));
+    }
+
+    #[test]
+    fn test_undeclared_days() {
+        let lib = "pub use utils::Error;\n\npub mod day_01;\n  mod day_03;\n// mod day_04;\n";
+
+        assert_eq!(vec![2, 4], undeclared_days(lib, &[1, 2, 3, 4]));
+        assert!(undeclared_days(lib, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_render_days() {
+        let rendered = render_days(25, &[1, 2]);
+
+        assert!(!rendered.contains("mod day_01"));
+        assert!(rendered.contains("pub use day_02::Day02;\n"));
+        assert!(rendered.contains("pub const YEAR: u16 = 25;\n"));
+        assert!(rendered.contains("    utils::Puzzle::new::<Day02>(YEAR, 2),\n"));
+    }
+}
@@ -0,0 +1,79 @@
+//! Arithmetic that returns an [`Error`] on overflow, rather than wrapping in a
+//! release build and panicking in a debug one. Each function takes the name of
+//! what is being worked out so the error can say what overflowed.
+
+use crate::Error;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, One, Zero};
+
+/// The integer types that answers can be accumulated in. Solutions that may
+/// need more than 64 bits can be written against this and run with `i128` or
+/// `num_bigint::BigInt`.
+pub trait Integer: Clone + Zero + One + CheckedAdd + CheckedSub + CheckedMul {}
+
+impl<T: Clone + Zero + One + CheckedAdd + CheckedSub + CheckedMul> Integer for T {}
+
+pub fn add<T: CheckedAdd>(a: T, b: T, operation: &str) -> Result<T, Error> {
+    a.checked_add(&b).ok_or_else(|| Error::overflow(operation))
+}
+
+pub fn sub<T: CheckedSub>(a: T, b: T, operation: &str) -> Result<T, Error> {
+    a.checked_sub(&b).ok_or_else(|| Error::overflow(operation))
+}
+
+pub fn mul<T: CheckedMul>(a: T, b: T, operation: &str) -> Result<T, Error> {
+    a.checked_mul(&b).ok_or_else(|| Error::overflow(operation))
+}
+
+pub fn sum<T: CheckedAdd + Zero>(
+    values: impl IntoIterator<Item = T>,
+    operation: &str,
+) -> Result<T, Error> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |total, value| add(total, value, operation))
+}
+
+pub fn product<T: CheckedMul + One>(
+    values: impl IntoIterator<Item = T>,
+    operation: &str,
+) -> Result<T, Error> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |total, value| mul(total, value, operation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reason;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_checked_ops() {
+        assert_eq!(5, add(2i64, 3, "total").unwrap());
+        assert_eq!(-1, sub(2i64, 3, "total").unwrap());
+        assert_eq!(6, mul(2i64, 3, "total").unwrap());
+        assert_eq!(10, sum([1i64, 2, 3, 4], "total").unwrap());
+        assert_eq!(24, product([1i64, 2, 3, 4], "total").unwrap());
+        assert_eq!(0, sum(Vec::<i64>::new(), "total").unwrap());
+        assert_eq!(1, product(Vec::<i64>::new(), "total").unwrap());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let err = product([i64::MAX, 2], "column total").unwrap_err();
+
+        assert!(matches!(err.reason, Reason::Overflow(ref op) if op == "column total"));
+        assert!(add(i8::MAX, 1, "count").is_err());
+        assert!(sub(u8::MIN, 1, "count").is_err());
+    }
+
+    #[test]
+    fn test_wider_integers() {
+        let wide = product([i64::MAX as i128, 2], "total").unwrap();
+        let big = product([BigInt::from(u128::MAX), BigInt::from(2)], "total").unwrap();
+
+        assert_eq!(i64::MAX as i128 * 2, wide);
+        assert_eq!(BigInt::from(u128::MAX) * 2, big);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::Part;
+use std::{
+    fmt::{self, Write},
+    io, num,
+};
+
+/// Why a puzzle could not be solved.
+#[derive(Debug, thiserror::Error)]
+pub enum Reason {
+    #[error(transparent)]
+    IO(#[from] io::Error),
+
+    #[error(transparent)]
+    InvalidInt(#[from] num::ParseIntError),
+
+    #[error(transparent)]
+    InvalidTryInt(#[from] num::TryFromIntError),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("{0} overflowed")]
+    Overflow(String),
+}
+
+/// The error shared by every solution. Along with the reason it records where
+/// it happened, as far as that is known.
+#[derive(Debug)]
+pub struct Error {
+    pub reason: Reason,
+    // Boxed to keep the `Result`s that carry the error small.
+    pub context: Box<Context>,
+}
+
+/// The day and part being solved, the input file, line and column, and the
+/// offending text.
+#[derive(Debug, Default)]
+pub struct Context {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub source_line: Option<String>,
+    pub text: Option<String>,
+}
+
+impl Error {
+    /// Input that doesn't have the expected shape, e.g.
+    /// `Error::invalid_input("expected L/R followed by digits").with_text("X12")`.
+    pub fn invalid_input(reason: impl Into<String>) -> Self {
+        Self::from(Reason::InvalidInput(reason.into()))
+    }
+
+    /// Arithmetic that went out of range, named after what was being worked
+    /// out, e.g. `Error::overflow("column total")`.
+    pub fn overflow(operation: impl Into<String>) -> Self {
+        Self::from(Reason::Overflow(operation.into()))
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.context.text.get_or_insert_with(|| text.into());
+        self
+    }
+
+    /// Line numbers start at 1. A line that is already set is kept, since it
+    /// was recorded closer to where the error happened.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.context.line.get_or_insert(line);
+        self
+    }
+
+    /// Columns start at 1 and point at the start of the offending text.
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.context.column.get_or_insert(column);
+        self
+    }
+
+    /// The full input line the error came from, shown by `diagnostic`.
+    pub fn with_source_line(mut self, source_line: impl Into<String>) -> Self {
+        self.context
+            .source_line
+            .get_or_insert_with(|| source_line.into());
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.context.file.get_or_insert_with(|| file.into());
+        self
+    }
+
+    pub fn in_day(mut self, year: u16, day: u8) -> Self {
+        self.context.year.get_or_insert(year);
+        self.context.day.get_or_insert(day);
+        self
+    }
+
+    pub fn in_part(mut self, part: Part) -> Self {
+        self.context.part.get_or_insert(part);
+        self
+    }
+
+    /// Renders the error like a compiler diagnostic, with a caret under the
+    /// offending text:
+    ///
+    /// ```text
+    ///   --> resources/aoc_25/day_01.txt:17:1
+    ///    |
+    /// 17 | X12
+    ///    | ^^^ expected L/R followed by digits
+    /// ```
+    ///
+    /// There is nothing to show unless the line the error came from is known.
+    pub fn diagnostic(&self) -> Option<String> {
+        let line = self.context.line?;
+        let source_line = self.context.source_line.as_deref()?;
+        let (column, width) = self.span(source_line);
+        let gutter = " ".repeat(line.to_string().len());
+        let mut out = String::new();
+
+        // Writing to a String can't fail.
+        let _ = writeln!(
+            out,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.context.file.as_deref().unwrap_or("<input>"),
+            line,
+            column + 1
+        );
+        let _ = writeln!(out, "{} |", gutter);
+        let _ = writeln!(out, "{} | {}", line, source_line);
+        let _ = write!(
+            out,
+            "{} | {}{} {}",
+            gutter,
+            " ".repeat(column),
+            "^".repeat(width),
+            self.reason
+        );
+
+        Some(out)
+    }
+
+    // The zero based column and width to underline, in characters. Without a
+    // column the offending text is looked for in the line, and failing that the
+    // whole line is underlined.
+    fn span(&self, source_line: &str) -> (usize, usize) {
+        let line_width = source_line.chars().count();
+        let text_width = self.context.text.as_ref().map(|text| text.chars().count());
+
+        let column = match (self.context.column, &self.context.text) {
+            (Some(column), _) => Some(column.saturating_sub(1)),
+            (None, Some(text)) => source_line
+                .find(text.as_str())
+                .map(|start| source_line[..start].chars().count()),
+            (None, None) => None,
+        };
+
+        match (column, text_width) {
+            (Some(column), Some(width)) => (column, width.max(1)),
+            (Some(column), None) => (column, 1),
+            (None, _) => (0, line_width.max(1)),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut location = vec![];
+
+        if let Some(year) = self.context.year {
+            location.push(format!("year {}", year));
+        }
+
+        if let Some(day) = self.context.day {
+            location.push(format!("day {:02}", day));
+        }
+
+        if let Some(part) = self.context.part {
+            location.push(format!("part {}", part));
+        }
+
+        if let Some(file) = &self.context.file {
+            location.push(file.clone());
+        }
+
+        if let Some(line) = self.context.line {
+            location.push(format!("line {}", line));
+        }
+
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+
+        write!(f, "{}", self.reason)?;
+
+        if let Some(text) = &self.context.text {
+            write!(f, ", got {:?}", text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.reason)
+    }
+}
+
+impl From<Reason> for Error {
+    fn from(reason: Reason) -> Self {
+        Self {
+            reason,
+            context: Box::default(),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::from(Reason::from(err))
+    }
+}
+
+impl From<num::ParseIntError> for Error {
+    fn from(err: num::ParseIntError) -> Self {
+        Self::from(Reason::from(err))
+    }
+}
+
+impl From<num::TryFromIntError> for Error {
+    fn from(err: num::TryFromIntError) -> Self {
+        Self::from(Reason::from(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_with_context() {
+        let err = Error::invalid_input("expected L/R followed by digits")
+            .with_text("X12")
+            .at_line(17)
+            .in_day(25, 1);
+
+        assert_eq!(
+            "year 25, day 01, line 17: expected L/R followed by digits, got \"X12\"",
+            err.to_string()
+        );
+        assert_eq!(Some(25), err.context.year);
+    }
+
+    #[test]
+    fn test_display_overflow() {
+        let err = Error::overflow("column total")
+            .in_day(25, 6)
+            .in_part(Part::One);
+
+        assert_eq!(
+            "year 25, day 06, part 1: column total overflowed",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_without_context() {
+        let err = Error::from("x".parse::<i32>().unwrap_err());
+
+        assert_eq!("invalid digit found in string", err.to_string());
+        assert_eq!(
+            "year 24, day 03, part 2: invalid digit found in string",
+            err.in_day(24, 3).in_part(Part::Two).to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_file() {
+        let missing = Error::from(io::Error::from(io::ErrorKind::NotFound))
+            .in_day(25, 2)
+            .in_file("resources/aoc_25/day_02.txt");
+        let with_diagnostic = Error::invalid_input("expected a range like 3-5")
+            .with_source_line("77")
+            .at_line(3)
+            .in_day(25, 5)
+            .in_file("resources/aoc_25/day_05.txt");
+
+        assert_eq!(
+            "year 25, day 02, resources/aoc_25/day_02.txt: entity not found",
+            missing.to_string()
+        );
+        assert_eq!(
+            "year 25, day 05, resources/aoc_25/day_05.txt, line 3: expected a range like 3-5",
+            with_diagnostic.to_string()
+        );
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let err = Error::invalid_input("expected L/R followed by digits")
+            .with_text("X12")
+            .with_source_line("X12")
+            .at_line(17)
+            .in_file("resources/aoc_25/day_01.txt");
+
+        let expected = [
+            "  --> resources/aoc_25/day_01.txt:17:1",
+            "   |",
+            "17 | X12",
+            "   | ^^^ expected L/R followed by digits",
+        ];
+
+        assert_eq!(expected.join("\n"), err.diagnostic().unwrap());
+    }
+
+    #[test]
+    fn test_diagnostic_span() {
+        let found = Error::invalid_input("bad")
+            .with_text("x")
+            .with_source_line("1,x,3")
+            .at_line(2);
+        let column = Error::invalid_input("bad")
+            .with_text("3")
+            .with_source_line("3,2,3")
+            .at_column(5)
+            .at_line(2);
+        let whole_line = Error::invalid_input("bad")
+            .with_source_line("abc")
+            .at_line(2);
+
+        assert!(found
+            .diagnostic()
+            .unwrap()
+            .ends_with("2 | 1,x,3\n  |   ^ bad"));
+        assert!(column.diagnostic().unwrap().ends_with("  |     ^ bad"));
+        assert!(whole_line.diagnostic().unwrap().ends_with("  | ^^^ bad"));
+        assert_eq!(None, Error::invalid_input("bad").at_line(2).diagnostic());
+    }
+
+    #[test]
+    fn test_innermost_context_is_kept() {
+        let err = Error::invalid_input("bad")
+            .with_text("a")
+            .at_line(2)
+            .at_line(9);
+
+        assert_eq!(Some(2), err.context.line);
+        assert_eq!(Some(String::from("a")), err.with_text("b").context.text);
+    }
+}
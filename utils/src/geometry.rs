@@ -0,0 +1,381 @@
+//! Points and vectors on a 2D or 3D integer lattice, along with the distance
+//! metrics and directions that spatial puzzles tend to need.
+//!
+//! In 2D, `x` increases to the right and `y` increases downwards, so a point
+//! lines up with a [`Grid`](crate::Grid) position: `x` is the column and `y`
+//! is the row.
+//!
+//! The arithmetic operators behave like the ones on `i64`, so they panic on
+//! overflow in a debug build. The integer metrics are checked instead, as far
+//! apart points can easily be further apart than an `i64` can say.
+
+use crate::{checked, Error};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+macro_rules! lattice {
+    ($point:ident, $vector:ident, $($axis:ident),+) => {
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct $point {
+            $(pub $axis: i64),+
+        }
+
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct $vector {
+            $(pub $axis: i64),+
+        }
+
+        impl $point {
+            pub const ORIGIN: Self = Self { $($axis: 0),+ };
+
+            pub const fn new($($axis: i64),+) -> Self {
+                Self { $($axis),+ }
+            }
+
+            /// The vector that takes this point to `other`.
+            pub fn vector_to(&self, other: &Self) -> Result<$vector, Error> {
+                Ok($vector {
+                    $($axis: checked::sub(other.$axis, self.$axis, "vector between points")?),+
+                })
+            }
+
+            pub fn manhattan_distance(&self, other: &Self) -> Result<i64, Error> {
+                self.vector_to(other)?.manhattan()
+            }
+
+            pub fn chebyshev_distance(&self, other: &Self) -> Result<i64, Error> {
+                self.vector_to(other)?.chebyshev()
+            }
+
+            pub fn squared_euclidean_distance(&self, other: &Self) -> Result<i64, Error> {
+                self.vector_to(other)?.squared_euclidean()
+            }
+
+            /// Worked out in floating point, so unlike the other metrics it
+            /// can't overflow.
+            pub fn euclidean_distance(&self, other: &Self) -> f64 {
+                let squares = [$((other.$axis as f64 - self.$axis as f64).powi(2)),+];
+
+                squares.iter().sum::<f64>().sqrt()
+            }
+        }
+
+        impl $vector {
+            pub const ZERO: Self = Self { $($axis: 0),+ };
+
+            pub const fn new($($axis: i64),+) -> Self {
+                Self { $($axis),+ }
+            }
+
+            /// The sum of the absolute components, i.e. the taxicab length.
+            pub fn manhattan(&self) -> Result<i64, Error> {
+                checked::sum([$(abs(self.$axis, "manhattan distance")?),+], "manhattan distance")
+            }
+
+            /// The largest absolute component, i.e. the number of king's moves.
+            pub fn chebyshev(&self) -> Result<i64, Error> {
+                let components = [$(abs(self.$axis, "chebyshev distance")?),+];
+
+                Ok(components.into_iter().max().unwrap_or_default())
+            }
+
+            pub fn squared_euclidean(&self) -> Result<i64, Error> {
+                let squares = [$(checked::mul(self.$axis, self.$axis, "squared distance")?),+];
+
+                checked::sum(squares, "squared distance")
+            }
+
+            pub fn euclidean(&self) -> f64 {
+                $point::ORIGIN.euclidean_distance(&$point { $($axis: self.$axis),+ })
+            }
+        }
+
+        impl Add<$vector> for $point {
+            type Output = $point;
+
+            fn add(self, vector: $vector) -> $point {
+                $point { $($axis: self.$axis + vector.$axis),+ }
+            }
+        }
+
+        impl AddAssign<$vector> for $point {
+            fn add_assign(&mut self, vector: $vector) {
+                *self = *self + vector;
+            }
+        }
+
+        impl Sub<$vector> for $point {
+            type Output = $point;
+
+            fn sub(self, vector: $vector) -> $point {
+                $point { $($axis: self.$axis - vector.$axis),+ }
+            }
+        }
+
+        impl SubAssign<$vector> for $point {
+            fn sub_assign(&mut self, vector: $vector) {
+                *self = *self - vector;
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vector;
+
+            fn sub(self, other: $point) -> $vector {
+                $vector { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl Add for $vector {
+            type Output = $vector;
+
+            fn add(self, other: $vector) -> $vector {
+                $vector { $($axis: self.$axis + other.$axis),+ }
+            }
+        }
+
+        impl AddAssign for $vector {
+            fn add_assign(&mut self, other: $vector) {
+                *self = *self + other;
+            }
+        }
+
+        impl Sub for $vector {
+            type Output = $vector;
+
+            fn sub(self, other: $vector) -> $vector {
+                $vector { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl SubAssign for $vector {
+            fn sub_assign(&mut self, other: $vector) {
+                *self = *self - other;
+            }
+        }
+
+        impl Mul<i64> for $vector {
+            type Output = $vector;
+
+            fn mul(self, scale: i64) -> $vector {
+                $vector { $($axis: self.$axis * scale),+ }
+            }
+        }
+
+        impl Neg for $vector {
+            type Output = $vector;
+
+            fn neg(self) -> $vector {
+                $vector { $($axis: -self.$axis),+ }
+            }
+        }
+    };
+}
+
+lattice!(Point2, Vector2, x, y);
+lattice!(Point3, Vector3, x, y, z);
+
+fn abs(value: i64, operation: &str) -> Result<i64, Error> {
+    value
+        .checked_abs()
+        .ok_or_else(|| Error::overflow(operation))
+}
+
+impl Point2 {
+    /// The point at a grid's `(row, column)` position.
+    pub fn from_position((row, column): (usize, usize)) -> Self {
+        // A grid can't hold more than `isize::MAX` cells, so its positions fit.
+        Self::new(column as i64, row as i64)
+    }
+
+    /// The grid `(row, column)` position of this point, or `None` if it is
+    /// above or left of the grid.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+}
+
+/// The four orthogonal directions, in clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns 90 degrees counter clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// A single step in this direction.
+    pub fn vector(self) -> Vector2 {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::Right => Vector2::new(1, 0),
+            Self::Down => Vector2::new(0, 1),
+            Self::Left => Vector2::new(-1, 0),
+        }
+    }
+}
+
+/// The eight directions to a neighboring cell, diagonals included, in
+/// clockwise order.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45 degrees counter clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// A single step in this direction.
+    pub fn vector(self) -> Vector2 {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::UpRight => Vector2::new(1, -1),
+            Self::Right => Vector2::new(1, 0),
+            Self::DownRight => Vector2::new(1, 1),
+            Self::Down => Vector2::new(0, 1),
+            Self::DownLeft => Vector2::new(-1, 1),
+            Self::Left => Vector2::new(-1, 0),
+            Self::UpLeft => Vector2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let mut point = Point2::new(3, 4);
+        let step = Vector2::new(1, -2);
+
+        assert_eq!(Point2::new(4, 2), point + step);
+        assert_eq!(Point2::new(2, 6), point - step);
+        assert_eq!(Vector2::new(3, 4), point - Point2::ORIGIN);
+        assert_eq!(Vector2::new(-3, 6), -step * 3);
+
+        point += step;
+        point -= step * 2;
+
+        assert_eq!(Point2::new(2, 6), point);
+        assert_eq!(
+            Point3::new(1, 1, 1),
+            Point3::ORIGIN + Vector3::new(2, 3, 4) - Vector3::new(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn test_metrics() {
+        let a = Point3::new(162, 817, 812);
+        let b = Point3::new(425, 690, 689);
+
+        assert_eq!(513, a.manhattan_distance(&b).unwrap());
+        assert_eq!(263, a.chebyshev_distance(&b).unwrap());
+        assert_eq!(100427, a.squared_euclidean_distance(&b).unwrap());
+        assert!((a.euclidean_distance(&b) - 100427f64.sqrt()).abs() < 1e-9);
+        assert_eq!(5.0, Vector2::new(3, -4).euclidean());
+    }
+
+    #[test]
+    fn test_metric_overflow() {
+        let near = Point2::new(i64::MIN, 0);
+        let far = Point2::new(i64::MAX, 0);
+
+        assert_eq!(
+            "vector between points overflowed",
+            near.manhattan_distance(&far).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "squared distance overflowed",
+            Vector2::new(i64::MAX, 0)
+                .squared_euclidean()
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(near.euclidean_distance(&far).is_finite());
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction4::Right, Direction4::Up.turn_right());
+        assert_eq!(Direction4::Left, Direction4::Up.turn_left());
+        assert_eq!(Direction4::Down, Direction4::Up.opposite());
+        assert_eq!(Direction8::UpLeft, Direction8::Up.turn_left());
+        assert_eq!(Direction8::DownLeft, Direction8::UpRight.opposite());
+        assert_eq!(Direction8::Left, Direction8::from(Direction4::Left));
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction, direction.turn_right().turn_left());
+            assert_eq!(-direction.vector(), direction.opposite().vector());
+        }
+
+        let walked = Direction4::ALL
+            .iter()
+            .fold(Point2::ORIGIN, |point, direction| {
+                point + direction.vector()
+            });
+
+        assert_eq!(Point2::ORIGIN, walked);
+    }
+
+    #[test]
+    fn test_grid_positions() {
+        let point = Point2::from_position((2, 5));
+
+        assert_eq!(Point2::new(5, 2), point);
+        assert_eq!(Some((2, 5)), point.position());
+        assert_eq!(None, (point + Direction4::Up.vector() * 3).position());
+    }
+}
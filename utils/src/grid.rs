@@ -0,0 +1,441 @@
+use crate::{Direction4, Direction8, Error, Point2};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row. Positions are
+/// `(row, column)` pairs counted from the top left.
+///
+/// Every lookup is bounds checked, so neighbors off the edge of the grid are
+/// simply left out rather than needing a border of padding.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, which must all be as wide as the first. The
+    /// number of a row that isn't is recorded as the error's line.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(
+                Error::invalid_input(format!("expected every row to be {width} wide"))
+                    .at_line(index + 1),
+            );
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, column): (usize, usize)) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.0 * self.width + position.1])
+    }
+
+    /// The cell at `point`, where `x` is the column and `y` is the row.
+    pub fn get_point(&self, point: Point2) -> Option<&T> {
+        self.get(point.position()?)
+    }
+
+    pub fn get_point_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.get_mut(point.position()?)
+    }
+
+    /// The points a step away from `point` in each [`Direction4`] that are on
+    /// the grid, in clockwise order from above.
+    pub fn point_neighbors4(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        Direction4::ALL
+            .into_iter()
+            .map(move |direction| point + direction.vector())
+            .filter(|neighbor| self.get_point(*neighbor).is_some())
+    }
+
+    /// The points a step away from `point` in each [`Direction8`] that are on
+    /// the grid, in clockwise order from above.
+    pub fn point_neighbors8(&self, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| point + direction.vector())
+            .filter(|neighbor| self.get_point(*neighbor).is_some())
+    }
+
+    /// The positions directly above, left, right and below, in that order.
+    pub fn neighbors4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(position, &ORTHOGONAL)
+    }
+
+    /// The positions surrounding `position`, diagonals included, from the top
+    /// left to the bottom right.
+    pub fn neighbors8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(position, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        (row, column): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets
+            .iter()
+            .filter_map(move |&(row_offset, column_offset)| {
+                let neighbor = (
+                    row.checked_add_signed(row_offset)?,
+                    column.checked_add_signed(column_offset)?,
+                );
+
+                self.contains(neighbor).then_some(neighbor)
+            })
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).filter_map(|row| self.row(row))
+    }
+
+    /// The cells of `column` from top to bottom, or nothing if it is past the
+    /// right edge.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> + '_ {
+        (0..self.height).filter_map(move |row| self.get((row, column)))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    /// Swaps rows for columns, mirroring the grid along its main diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |(row, column)| (column, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+
+        self.remap(height, self.width, |(row, column)| {
+            (height - 1 - column, row)
+        })
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+
+        self.remap(self.height, width, |(row, column)| {
+            (column, width - 1 - row)
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+
+        self.remap(width, self.height, |(row, column)| {
+            (row, width - 1 - column)
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+
+        self.remap(self.width, height, |(row, column)| {
+            (height - 1 - row, column)
+        })
+    }
+
+    // Builds a grid of the given size, taking each cell from the position in
+    // this grid that `source` maps it to.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn((usize, usize)) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the position is outside the grid; use `get` when it might be.
+    fn index(&self, position: (usize, usize)) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside a {}x{} grid",
+                position, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside a {}x{} grid", position, width, height),
+        }
+    }
+}
+
+/// Writes one line per row, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1 2 3
+    // 4 5 6
+    fn numbers() -> Grid<i32> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    fn chars(text: &str) -> Grid<char> {
+        Grid::from_rows(text.lines().map(|line| line.chars().collect()).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = numbers();
+        let ragged = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(
+            "line 3: expected every row to be 2 wide",
+            ragged.to_string()
+        );
+        assert!(Grid::<i32>::from_rows(vec![]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = numbers();
+
+        assert_eq!(Some(&6), grid.get((1, 2)));
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+
+        grid[(0, 1)] = 9;
+        *grid.get_mut((1, 0)).unwrap() = 7;
+
+        assert_eq!(vec![1, 9, 3, 7, 5, 6], grid.cells);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = numbers();
+
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            grid.neighbors4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (0, 2), (1, 1)],
+            grid.neighbors4((0, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)],
+            grid.neighbors8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.neighbors8((5, 5)).count());
+    }
+
+    #[test]
+    fn test_points() {
+        let mut grid = numbers();
+
+        assert_eq!(Some(&6), grid.get_point(Point2::new(2, 1)));
+        assert_eq!(None, grid.get_point(Point2::new(-1, 0)));
+        assert_eq!(None, grid.get_point(Point2::new(3, 0)));
+
+        *grid.get_point_mut(Point2::new(0, 1)).unwrap() = 7;
+        assert_eq!(Some(&7), grid.get((1, 0)));
+
+        assert_eq!(
+            vec![Point2::new(1, 0), Point2::new(0, 1)],
+            grid.point_neighbors4(Point2::new(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                Point2::new(1, 0),
+                Point2::new(2, 0),
+                Point2::new(2, 1),
+                Point2::new(0, 1),
+                Point2::new(0, 0)
+            ],
+            grid.point_neighbors8(Point2::new(1, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = numbers();
+        let columns: Vec<Vec<i32>> = grid
+            .columns()
+            .map(|column| column.copied().collect())
+            .collect();
+
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![vec![1, 4], vec![2, 5], vec![3, 6]], columns);
+        assert_eq!(None, grid.row(2));
+        assert_eq!(0, grid.column(3).count());
+    }
+
+    #[test]
+    fn test_find_all() {
+        let grid = chars("@.@\n.@.");
+
+        assert_eq!(
+            vec![(0, 0), (0, 2), (1, 1)],
+            grid.find_all(&'@').collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.find_all(&'x').count());
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = numbers();
+
+        assert_eq!("14\n25\n36", grid.transpose().to_string());
+        assert_eq!("41\n52\n63", grid.rotate_clockwise().to_string());
+        assert_eq!("36\n25\n14", grid.rotate_counter_clockwise().to_string());
+        assert_eq!("321\n654", grid.flip_horizontal().to_string());
+        assert_eq!("456\n123", grid.flip_vertical().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counter_clockwise());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("ab\ncd", chars("ab\ncd").to_string());
+        assert_eq!("", Grid::<char>::from_rows(vec![]).unwrap().to_string());
+    }
+}
@@ -1,23 +1,159 @@
+mod answer;
+pub mod build;
+pub mod checked;
+mod error;
+mod geometry;
+mod grid;
+mod part;
+mod range_set;
+mod registry;
+mod solution;
+
 use std::{
     fs::File,
-    io::{self, BufRead},
+    io::{self, BufRead, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-pub struct FileReader(String);
+pub use answer::Answer;
+pub use error::{Context, Error, Reason};
+pub use geometry::{Direction4, Direction8, Point2, Point3, Vector2, Vector3};
+pub use grid::Grid;
+pub use part::Part;
+pub use range_set::RangeSet;
+pub use registry::{Entry, Outcome, Parsed, Puzzle, Registry};
+pub use solution::Solution;
+
+/// A puzzle input, either a file on disk that is opened each time it is read
+/// or text already held in memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileReader(Source);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Source {
+    File(PathBuf),
+    Buffer(&'static str, Arc<[u8]>),
+}
 
 impl FileReader {
-    pub fn new(filepath: &str) -> Self {
-        Self(String::from(filepath))
+    pub fn new(filepath: impl AsRef<Path>) -> Self {
+        Self(Source::File(filepath.as_ref().to_path_buf()))
+    }
+
+    pub fn from_text(text: &str) -> Self {
+        Self(Source::Buffer("<input>", Arc::from(text.as_bytes())))
+    }
+
+    /// Reads everything from `reader` up front so the input can be read (and
+    /// cloned) as many times as needed.
+    pub fn from_reader(reader: impl Read) -> io::Result<Self> {
+        Self::buffered("<input>", reader)
+    }
+
+    pub fn stdin() -> io::Result<Self> {
+        Self::buffered("<stdin>", io::stdin().lock())
+    }
+
+    fn buffered(name: &'static str, mut reader: impl Read) -> io::Result<Self> {
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer)?;
+
+        Ok(Self(Source::Buffer(name, Arc::from(buffer))))
+    }
+
+    /// The file path, or a placeholder for input held in memory, for use in
+    /// error messages.
+    pub fn name(&self) -> String {
+        match &self.0 {
+            Source::File(path) => path.display().to_string(),
+            Source::Buffer(name, _) => name.to_string(),
+        }
+    }
+
+    fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match &self.0 {
+            Source::File(path) => Box::new(io::BufReader::new(File::open(path)?)),
+            Source::Buffer(_, buffer) => Box::new(io::Cursor::new(Arc::clone(buffer))),
+        })
+    }
+
+    pub fn read_lines(&self) -> io::Result<io::Lines<Box<dyn BufRead>>> {
+        Ok(self.open()?.lines())
     }
 
-    pub fn read_lines(&self) -> io::Result<io::Lines<io::BufReader<File>>> {
-        let file = File::open(self.0.as_str())?;
-        Ok(io::BufReader::new(file).lines())
+    /// Parses every line with `parse`, recording the line and its number on any
+    /// error so that it can be traced back to the input.
+    pub fn parse_lines<T, E>(
+        &self,
+        mut parse: impl FnMut(String) -> Result<T, E>,
+    ) -> Result<Vec<T>, Error>
+    where
+        E: Into<Error>,
+    {
+        self.read_lines()?
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line?;
+
+                parse(line.clone())
+                    .map_err(|err| err.into().at_line(index + 1).with_source_line(line))
+            })
+            .collect()
+    }
+
+    /// Splits the input into sections of consecutive lines, separated by one or
+    /// more blank lines. Blank lines before the first section and after the
+    /// last are ignored.
+    pub fn read_sections(&self) -> Result<Vec<Section>, Error> {
+        let mut sections = vec![];
+        let mut current: Option<Section> = None;
+
+        for (index, line) in self.read_lines()?.enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                sections.extend(current.take());
+            } else {
+                current
+                    .get_or_insert_with(|| Section::new(index + 1))
+                    .lines
+                    .push(line);
+            }
+        }
+        sections.extend(current);
+
+        Ok(sections)
+    }
+
+    /// Reads the input as a grid of characters, checking that every row is as
+    /// wide as the first. Blank lines at the end are ignored.
+    pub fn read_grid(&self) -> Result<Grid<char>, Error> {
+        let mut lines = self.read_lines()?.collect::<io::Result<Vec<String>>>()?;
+
+        while lines.last().is_some_and(String::is_empty) {
+            lines.pop();
+        }
+
+        Grid::from_rows(lines.iter().map(|line| line.chars().collect()).collect()).map_err(|err| {
+            match err.context.line.and_then(|line| lines.get(line - 1)) {
+                Some(source_line) => err.with_source_line(source_line),
+                None => err,
+            }
+        })
+    }
+
+    /// Reads the whole input, where `read_string` stops at the end of the
+    /// first line.
+    pub fn read_to_string(&self) -> io::Result<String> {
+        let mut text = String::new();
+        self.open()?.read_to_string(&mut text)?;
+
+        Ok(text)
     }
 
     pub fn read_string(&self) -> io::Result<String> {
-        let file = File::open(self.0.as_str())?;
-        let Some(first_line) = io::BufReader::new(file).lines().next() else {
+        let Some(first_line) = self.open()?.lines().next() else {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 "No data found in file",
@@ -28,6 +164,49 @@ impl FileReader {
     }
 }
 
+/// A run of consecutive non-blank lines from an input, as read by
+/// `FileReader::read_sections`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section {
+    first_line: usize,
+    lines: Vec<String>,
+}
+
+impl Section {
+    fn new(first_line: usize) -> Self {
+        Self {
+            first_line,
+            lines: vec![],
+        }
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Parses every line with `parse`, like `FileReader::parse_lines`. Errors
+    /// record the line's number in the whole input, not just this section.
+    pub fn parse_lines<T, E>(
+        &self,
+        mut parse: impl FnMut(String) -> Result<T, E>,
+    ) -> Result<Vec<T>, Error>
+    where
+        E: Into<Error>,
+    {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                parse(line.clone()).map_err(|err| {
+                    err.into()
+                        .at_line(self.first_line + index)
+                        .with_source_line(line)
+                })
+            })
+            .collect()
+    }
+}
+
 pub struct StringUtils;
 
 impl StringUtils {
@@ -87,6 +266,90 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_from_text() {
+        let file_reader = FileReader::from_text("abc\ndef\n");
+        let lines: Vec<String> = file_reader
+            .read_lines()
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(vec!["abc", "def"], lines);
+        assert_eq!(
+            String::from("abc"),
+            file_reader.clone().read_string().unwrap()
+        );
+        assert!(FileReader::from_text("").read_string().is_err());
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let file_reader = FileReader::from_text("1\n2\nthree\n4");
+
+        assert_eq!(
+            vec![1, 2],
+            FileReader::from_text("1\n2")
+                .parse_lines(|line| line.parse::<i32>())
+                .unwrap()
+        );
+        let err = file_reader
+            .parse_lines(|line| line.parse::<i32>())
+            .unwrap_err();
+
+        assert_eq!(Some(3), err.context.line);
+        assert_eq!(Some(String::from("three")), err.context.source_line);
+    }
+
+    #[test]
+    fn test_read_to_string() {
+        let file_reader = FileReader::from_text("abc\n\ndef\n");
+
+        assert_eq!("abc\n\ndef\n", file_reader.read_to_string().unwrap());
+        assert_eq!("", FileReader::from_text("").read_to_string().unwrap());
+    }
+
+    #[test]
+    fn test_read_sections() {
+        let file_reader = FileReader::from_text("\n1-2\n3-4\n\n \n5\nsix\n\n");
+        let sections = file_reader.read_sections().unwrap();
+
+        assert_eq!(2, sections.len());
+        assert_eq!(["1-2", "3-4"], sections[0].lines());
+        assert_eq!(["5", "six"], sections[1].lines());
+
+        let err = sections[1]
+            .parse_lines(|line| line.parse::<i32>())
+            .unwrap_err();
+
+        assert_eq!(Some(7), err.context.line);
+        assert_eq!(Some(String::from("six")), err.context.source_line);
+        assert!(FileReader::from_text("")
+            .read_sections()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let grid = FileReader::from_text("ab\ncd\n\n").read_grid().unwrap();
+        let err = FileReader::from_text("ab\ncde\n").read_grid().unwrap_err();
+
+        assert_eq!("ab\ncd", grid.to_string());
+        assert_eq!("line 2: expected every row to be 2 wide", err.to_string());
+        assert_eq!(Some(String::from("cde")), err.context.source_line);
+        assert!(FileReader::from_text("").read_grid().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let file_reader = FileReader::from_reader("one\ntwo".as_bytes()).unwrap();
+
+        assert_eq!(2, file_reader.read_lines().unwrap().count());
+        assert_eq!(2, file_reader.read_lines().unwrap().count());
+        assert_eq!("<input>", file_reader.name());
+    }
+
     #[test]
     fn test_partition_by() {
         assert_eq!(
@@ -0,0 +1,55 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("expected part 1 or 2, got \"{}\"", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!(Part::One, "1".parse::<Part>().unwrap());
+        assert_eq!(Part::Two, "2".parse::<Part>().unwrap());
+        assert!("3".parse::<Part>().is_err());
+        assert!("one".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_part_display() {
+        assert_eq!("1", Part::One.to_string());
+        assert_eq!("2", Part::Two.to_string());
+    }
+}
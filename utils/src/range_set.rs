@@ -0,0 +1,277 @@
+use crate::{
+    checked::{self, Integer},
+    Error,
+};
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as inclusive ranges. The ranges are kept sorted,
+/// with any that overlap or touch merged, so membership is a binary search and
+/// every value is covered by exactly one range.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Integer + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The merged ranges, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<T>> + '_ {
+        self.ranges.iter()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// The index of the merged range holding `value`, counting from the
+    /// lowest range as `iter` does.
+    pub fn find(&self, value: &T) -> Option<usize> {
+        let index = self.ranges.partition_point(|range| range.end() < value);
+
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start() <= value)
+            .then_some(index)
+    }
+
+    /// The number of values in the set, which can overflow `T` when the set
+    /// covers every value it can hold.
+    pub fn len(&self) -> Result<T, Error> {
+        self.ranges.iter().try_fold(T::zero(), |total, range| {
+            let span = checked::sub(range.end().clone(), range.start().clone(), "range length")?;
+            let length = checked::add(span, T::one(), "range length")?;
+
+            checked::add(total, length, "range length")
+        })
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = range.into_inner();
+
+        // Every range from `first` up to `last` overlaps or touches the new one.
+        let first = self
+            .ranges
+            .partition_point(|range| Self::gap_between(range.end(), &start));
+        let last = self
+            .ranges
+            .partition_point(|range| !Self::gap_between(&end, range.start()));
+
+        if first < last {
+            start = start.min(self.ranges[first].start().clone());
+            end = end.max(self.ranges[last - 1].end().clone());
+        }
+
+        self.ranges.splice(first..last, [start..=end]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (start, end) = range.into_inner();
+
+        // Every range from `first` up to `last` overlaps the removed one.
+        let first = self.ranges.partition_point(|range| *range.end() < start);
+        let last = self.ranges.partition_point(|range| *range.start() <= end);
+
+        if first >= last {
+            return;
+        }
+
+        // Whatever sticks out either side of the removed range is kept.
+        let mut kept = vec![];
+        let head_start = self.ranges[first].start().clone();
+        if let Some(before) = start.checked_sub(&T::one()).filter(|_| head_start < start) {
+            kept.push(head_start..=before);
+        }
+
+        let tail_end = self.ranges[last - 1].end().clone();
+        if let Some(after) = end.checked_add(&T::one()).filter(|_| tail_end > end) {
+            kept.push(after..=tail_end);
+        }
+
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range.clone());
+        }
+
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut mine, mut theirs) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            let start = a.start().max(b.start()).clone();
+            let end = a.end().min(b.end()).clone();
+
+            if start <= end {
+                ranges.push(start..=end);
+            }
+
+            // The range that ends first can't overlap anything further on.
+            if a.end() < b.end() {
+                mine.next();
+            } else {
+                theirs.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range.clone());
+        }
+
+        difference
+    }
+
+    /// Everything within `bounds` that isn't in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+
+    // Whether there is at least one value strictly between `a` and `b`, so that
+    // ranges ending at `a` and starting at `b` can't be merged.
+    fn gap_between(a: &T, b: &T) -> bool {
+        a.checked_add(&T::one()).is_some_and(|next| next < *b)
+    }
+}
+
+impl<T: Integer + Ord> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer + Ord> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        for range in ranges {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = std::slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i64>]) -> RangeSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn ranges(set: &RangeSet<i64>) -> Vec<RangeInclusive<i64>> {
+        set.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let set = set(&[
+            10..=14,
+            3..=5,
+            16..=20,
+            12..=18,
+            6..=6,
+            RangeInclusive::new(30, 29),
+        ]);
+
+        assert_eq!(vec![3..=6, 10..=20], ranges(&set));
+    }
+
+    #[test]
+    fn test_insert_at_limits() {
+        let set = set(&[i64::MAX..=i64::MAX, i64::MIN..=0, 1..=i64::MAX - 1]);
+
+        assert_eq!(vec![i64::MIN..=i64::MAX], ranges(&set));
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&[3..=5, 10..=14]);
+
+        assert!(set.contains(&3));
+        assert!(set.contains(&5));
+        assert!(set.contains(&12));
+        assert!(!set.contains(&2));
+        assert!(!set.contains(&6));
+        assert!(!set.contains(&15));
+        assert!(!RangeSet::new().contains(&0));
+    }
+
+    #[test]
+    fn test_find() {
+        let set = set(&[3..=5, 10..=14]);
+
+        assert_eq!(Some(0), set.find(&4));
+        assert_eq!(Some(1), set.find(&10));
+        assert_eq!(None, set.find(&7));
+        assert_eq!(None, set.find(&15));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set = set(&[1..=10, 20..=30]);
+        set.remove(5..=22);
+        set.remove(30..=40);
+        set.remove(RangeInclusive::new(8, 2));
+
+        assert_eq!(vec![1..=4, 23..=29], ranges(&set));
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(14, set(&[3..=5, 10..=14, 16..=20, 12..=18]).len().unwrap());
+        assert_eq!(0, RangeSet::<i64>::new().len().unwrap());
+        assert_eq!(i64::MAX, set(&[1..=i64::MAX]).len().unwrap());
+        assert_eq!(
+            "range length overflowed",
+            set(&[0..=i64::MAX]).len().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=5, 10..=15]);
+        let b = set(&[4..=11, 20..=25]);
+
+        assert_eq!(vec![1..=15, 20..=25], ranges(&a.union(&b)));
+        assert_eq!(vec![4..=5, 10..=11], ranges(&a.intersection(&b)));
+        assert_eq!(vec![1..=3, 12..=15], ranges(&a.difference(&b)));
+        assert_eq!(vec![0..=0, 6..=9, 16..=20], ranges(&a.complement(0..=20)));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{Answer, Error, FileReader, Part, Solution};
+use std::{any::Any, sync::Arc, time::Duration};
+
+/// A single day's solution with its `Solution` type erased, so that days from
+/// every year can be stored and run side by side.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    parse: fn(FileReader) -> Result<Parsed, Error>,
+    solve: fn(&Parsed, Part) -> Result<Answer, Error>,
+}
+
+impl Puzzle {
+    pub const fn new<S>(year: u16, day: u8) -> Self
+    where
+        S: Solution,
+        S::Input: Send + Sync + 'static,
+    {
+        Self {
+            year,
+            day,
+            parse: parse::<S>,
+            solve: solve::<S>,
+        }
+    }
+
+    /// Parses the day's input, ready to be handed to either part. Errors are
+    /// tagged with the day and the input's name.
+    pub fn parse(&self, reader: FileReader) -> Result<Parsed, Error> {
+        let file = reader.name();
+
+        (self.parse)(reader).map_err(|err| err.in_day(self.year, self.day).in_file(file))
+    }
+
+    /// Solves a part from input parsed by this puzzle. Errors are tagged with
+    /// the day and the part.
+    pub fn solve(&self, input: &Parsed, part: Part) -> Result<Answer, Error> {
+        (self.solve)(input, part).map_err(|err| err.in_day(self.year, self.day).in_part(part))
+    }
+}
+
+/// A day's parsed input with its type erased. It is cheap to clone, so the
+/// same input can be shared by both parts, including across threads.
+#[derive(Clone)]
+pub struct Parsed(Arc<dyn Any + Send + Sync>);
+
+fn parse<S>(reader: FileReader) -> Result<Parsed, Error>
+where
+    S: Solution,
+    S::Input: Send + Sync + 'static,
+{
+    Ok(Parsed(Arc::new(S::parse(reader)?)))
+}
+
+fn solve<S>(input: &Parsed, part: Part) -> Result<Answer, Error>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    let Some(input) = input.0.downcast_ref::<S::Input>() else {
+        return Err(Error::invalid_input(
+            "input was parsed by a different puzzle",
+        ));
+    };
+
+    match part {
+        Part::One => S::part_one(input),
+        Part::Two => S::part_two(input),
+    }
+}
+
+/// The answer to a single part along with how long parsing the input and
+/// solving the part each took. A failure that can be traced back to a line of
+/// the input also comes with a rendered diagnostic pointing at it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub answer: Result<Answer, String>,
+    pub diagnostic: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl Outcome {
+    pub fn new(answer: Result<Answer, Error>, parse_time: Duration, solve_time: Duration) -> Self {
+        Self {
+            diagnostic: answer.as_ref().err().and_then(Error::diagnostic),
+            answer: answer.map_err(|err| err.to_string()),
+            parse_time,
+            solve_time,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+}
+
+/// Every registered puzzle, kept in year and day order.
+pub struct Registry(Vec<Puzzle>);
+
+impl Registry {
+    pub fn new<'a>(years: impl IntoIterator<Item = &'a [Puzzle]>) -> Self {
+        let mut puzzles: Vec<Puzzle> = years.into_iter().flatten().copied().collect();
+        puzzles.sort_by_key(|p| (p.year, p.day));
+
+        Self(puzzles)
+    }
+
+    pub fn puzzles(&self) -> &[Puzzle] {
+        &self.0
+    }
+
+    pub fn find(&self, year: u16, day: u8) -> Option<&Puzzle> {
+        self.0.iter().find(|p| p.year == year && p.day == day)
+    }
+
+    /// Every `(year, day, part)` that can be run, in order.
+    pub fn entries(&self) -> impl Iterator<Item = Entry> + '_ {
+        self.0.iter().flat_map(|puzzle| {
+            Part::ALL.into_iter().map(|part| Entry {
+                year: puzzle.year,
+                day: puzzle.day,
+                part,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        type Input = String;
+
+        fn parse(reader: FileReader) -> Result<Self::Input, Error> {
+            Ok(reader.read_string()?)
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer, Error> {
+            Ok(Answer::text(input))
+        }
+
+        fn part_two(input: &Self::Input) -> Result<Answer, Error> {
+            Err(Error::invalid_input("expected lowercase").with_text(input))
+        }
+    }
+
+    static LATER: [Puzzle; 2] = [Puzzle::new::<Echo>(25, 2), Puzzle::new::<Echo>(25, 1)];
+    static EARLIER: [Puzzle; 1] = [Puzzle::new::<Echo>(24, 9)];
+    static OTHER: [Puzzle; 1] = [Puzzle::new::<Count>(23, 1)];
+
+    struct Count;
+
+    impl Solution for Count {
+        type Input = usize;
+
+        fn parse(reader: FileReader) -> Result<Self::Input, Error> {
+            Ok(reader.read_string()?.len())
+        }
+
+        fn part_one(input: &Self::Input) -> Result<Answer, Error> {
+            Ok((*input).into())
+        }
+
+        fn part_two(input: &Self::Input) -> Result<Answer, Error> {
+            Ok((*input).into())
+        }
+    }
+
+    #[test]
+    fn test_registry_order() {
+        let registry = Registry::new([&LATER[..], &EARLIER[..]]);
+        let days: Vec<(u16, u8)> = registry.puzzles().iter().map(|p| (p.year, p.day)).collect();
+
+        assert_eq!(vec![(24, 9), (25, 1), (25, 2)], days);
+        assert_eq!(6, registry.entries().count());
+        assert_eq!(
+            Entry {
+                year: 24,
+                day: 9,
+                part: Part::One
+            },
+            registry.entries().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_puzzle_solve() {
+        let registry = Registry::new([&LATER[..]]);
+        let puzzle = registry.find(25, 1).unwrap();
+        let reader = FileReader::new("../test-resources/sample_file_one_line.txt");
+        let input = puzzle.parse(reader).unwrap();
+        let expected = Answer::text("File containing all data on first line.");
+
+        assert!(registry.find(24, 1).is_none());
+        assert_eq!(expected, puzzle.solve(&input, Part::One).unwrap());
+        assert_eq!(expected, puzzle.solve(&input.clone(), Part::One).unwrap());
+    }
+
+    #[test]
+    fn test_puzzle_parse_failure() {
+        let registry = Registry::new([&LATER[..]]);
+        let puzzle = registry.find(25, 2).unwrap();
+        let err = puzzle
+            .parse(FileReader::new("../test-resources/missing.txt"))
+            .err()
+            .unwrap();
+
+        assert!(err
+            .to_string()
+            .starts_with("year 25, day 02, ../test-resources/missing.txt: "));
+        assert_eq!(
+            Some(String::from("../test-resources/missing.txt")),
+            err.context.file
+        );
+        assert_eq!(None, err.diagnostic());
+    }
+
+    #[test]
+    fn test_puzzle_part_failure() {
+        let registry = Registry::new([&LATER[..]]);
+        let puzzle = registry.find(25, 1).unwrap();
+        let input = puzzle.parse(FileReader::from_text("abc")).unwrap();
+        let outcome = Outcome::new(
+            puzzle.solve(&input, Part::Two),
+            Duration::ZERO,
+            Duration::ZERO,
+        );
+
+        assert_eq!(
+            Err(String::from(
+                "year 25, day 01, part 2: expected lowercase, got \"abc\""
+            )),
+            outcome.answer
+        );
+    }
+
+    #[test]
+    fn test_puzzle_solve_wrong_input() {
+        let registry = Registry::new([&LATER[..], &OTHER[..]]);
+        let input = registry
+            .find(25, 1)
+            .unwrap()
+            .parse(FileReader::from_text("abc"))
+            .unwrap();
+
+        assert!(registry
+            .find(23, 1)
+            .unwrap()
+            .solve(&input, Part::One)
+            .is_err());
+    }
+}
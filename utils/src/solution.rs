@@ -0,0 +1,23 @@
+use crate::{Answer, Error, FileReader, Part};
+
+/// A single day's puzzle. The input is parsed once from a reader and then
+/// lent to whichever parts are being solved. A part that needs to change the
+/// input works on its own clone of it.
+pub trait Solution {
+    type Input;
+
+    fn parse(reader: FileReader) -> Result<Self::Input, Error>;
+
+    fn part_one(input: &Self::Input) -> Result<Answer, Error>;
+
+    fn part_two(input: &Self::Input) -> Result<Answer, Error>;
+
+    fn solve(reader: FileReader, part: Part) -> Result<Answer, Error> {
+        let input = Self::parse(reader)?;
+
+        match part {
+            Part::One => Self::part_one(&input),
+            Part::Two => Self::part_two(&input),
+        }
+    }
+}